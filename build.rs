@@ -1,171 +1,255 @@
-use chrono::{Datelike, Utc};
-use chrono_tz::US::Eastern;
-use reqwest::{blocking::Client, cookie::Jar, Url};
 use std::{
-    fs::{self, File},
-    io::Write,
-    path::Path,
-    sync::Arc,
+    env, fs,
+    path::{Path, PathBuf},
 };
 
-const FIRST_YEAR: i32 = 2015;
-
 fn main() {
     println!("cargo:rerun-if-changed=src/years");
-    let client = client();
-    let now = Utc::now().with_timezone(&Eastern);
-
-    let mut years_mod_file = File::create("src/years/mod.rs").unwrap();
-    let _ = writeln!(years_mod_file, "use std::collections::BTreeMap;");
-    for year in FIRST_YEAR..=now.year() {
-        let _ = writeln!(years_mod_file, "pub mod year{:04};", year);
-    }
-    let years_entries = (FIRST_YEAR..=now.year())
-        .filter_map(|year| generate_year(year, now, &client, "        ".to_string()))
-        .collect::<Vec<_>>()
-        .join("\n");
+    let years_dir = Path::new(&env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("src/years");
+    let years = years(&years_dir);
 
-    let _ = writeln!(
-        years_mod_file,
+    let (mods, entries): (Vec<_>, Vec<_>) = years
+        .iter()
+        .filter_map(|&year| generate_year(&years_dir, year, "        ".to_string()))
+        .unzip();
+    let mut registry = format!(
         concat!(
-            "\n",
-            "type DayInfo = (Box<dyn crate::DayGen + Sync>, &'static str, &'static str);\n\n",
+            "use std::collections::BTreeMap;\n\n",
+            "{mods}\n",
             "lazy_static::lazy_static! {{\n",
-            "    pub static ref YEARS: BTreeMap<i32, BTreeMap<u32, DayInfo>> = {{\n",
+            "    pub static ref YEARS: BTreeMap<i32, BTreeMap<u32, crate::DayInfo>> = {{\n",
             "        let mut map = BTreeMap::new();\n",
             "{entries}\n",
             "        map\n",
             "    }};\n",
-            "}}"
+            "}}\n"
         ),
-        entries = years_entries
+        mods = mods.join(""),
+        entries = entries.join("\n"),
     );
-}
 
-fn generate_year(year: i32, now: impl Datelike, client: &Client, indent: String) -> Option<String> {
-    let year_directory_name = format!("src/years/year{}", year);
-    let year_path = Path::new(&year_directory_name);
-    fs::create_dir_all(year_path).unwrap();
-    let year_mod_path = year_path.join("mod.rs");
-    if !year_mod_path.is_file() && !year_mod_path.exists() {
-        let mut year_mod_file = File::create(year_mod_path).unwrap();
-        let _ = writeln!(year_mod_file, "include!(\"./day_mods.rs\");");
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some() {
+        registry += &format!("\n{}\n", generate_embedded(&years_dir, &years));
     }
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("years.rs"), registry).unwrap();
+}
 
-    (now.year() > year || now.year() == year && now.month() == 12)
-        .then(|| {
-            let max_day = if now.year() > year || now.day() > 25 {
-                25
-            } else {
-                now.day()
-            };
+/// The years that have a `year{year}` directory, sorted.
+fn years(years_dir: &Path) -> Vec<i32> {
+    let mut years: Vec<i32> = fs::read_dir(years_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            if !entry.file_type().ok()?.is_dir() {
+                return None;
+            }
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("year")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    years.sort_unstable();
+    years
+}
 
-            let (mods, entries): (Vec<_>, Vec<_>) = (1..=max_day)
-                .filter_map(|day| {
-                    generate_day(
-                        day,
-                        year,
-                        year_path,
-                        &year_directory_name,
-                        client,
-                        format!("{}    ", indent),
-                    )
-                })
-                .unzip();
-            let year_day_mods_path = year_path.join("day_mods.rs");
-            let mut year_day_mods_file = File::create(year_day_mods_path).unwrap();
-            let _ = writeln!(year_day_mods_file, "{mods}", mods = mods.join("\n"));
-            (!entries.is_empty()).then(|| {
+/// A string literal of `path`, the generated code lives in `OUT_DIR` so
+/// everything it includes is referred to by its absolute path.
+fn literal(path: &Path) -> String {
+    format!("{:?}", path.display().to_string())
+}
+
+fn generate_embedded(years_dir: &Path, years: &[i32]) -> String {
+    let arms = years
+        .iter()
+        .flat_map(|&year| (1..=25).map(move |day| (year, day)))
+        .flat_map(|(year, day)| {
+            [
+                ("Input", format!("input{:02}.txt", day)),
+                ("Sample", format!("sample{:02}.txt", day)),
+            ]
+            .into_iter()
+            .map(move |(kind, file_name)| {
+                (
+                    kind,
+                    years_dir.join(format!("year{}", year)).join(file_name),
+                )
+            })
+            .filter(|(_, path)| path.is_file())
+            .map(move |(kind, path)| {
                 format!(
-                    concat!(
-                        "{indent}map.insert({year}, {{\n",
-                        "{indent}    let mut map = BTreeMap::<u32, DayInfo>::new();\n",
-                        "{entries}\n",
-                        "{indent}    map\n",
-                        "{indent}}});",
-                    ),
-                    year = year,
-                    entries = entries.join("\n"),
-                    indent = indent,
+                    "        ({}, {}, Kind::{}) => Some(include_str!({})),",
+                    year,
+                    day,
+                    kind,
+                    literal(&path)
                 )
             })
         })
-        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let sample_dir_arms = years
+        .iter()
+        .flat_map(|&year| (1..=25).map(move |day| (year, day)))
+        .filter_map(|(year, day)| {
+            let sample_dir = years_dir
+                .join(format!("year{}", year))
+                .join(format!("sample{:02}", day));
+            let mut files: Vec<_> = fs::read_dir(&sample_dir)
+                .ok()?
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter(|file_name| sample_dir.join(file_name).is_file())
+                .collect();
+            files.sort();
+            let files = files
+                .iter()
+                .map(|file_name| {
+                    format!(
+                        "({:?}, include_str!({}))",
+                        file_name,
+                        literal(&sample_dir.join(file_name))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            Some(format!("        ({}, {}) => &[{}],", year, day, files))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        concat!(
+            "pub(crate) fn embedded(year: i32, day: u32, kind: crate::inputs::Kind) -> Option<&'static str> {{\n",
+            "    use crate::inputs::Kind;\n",
+            "    match (year, day, kind) {{\n",
+            "{arms}\n",
+            "        _ => None,\n",
+            "    }}\n",
+            "}}\n\n",
+            "pub(crate) fn embedded_sample_dir(year: i32, day: u32) -> &'static [(&'static str, &'static str)] {{\n",
+            "    match (year, day) {{\n",
+            "{sample_dir_arms}\n",
+            "        _ => &[],\n",
+            "    }}\n",
+            "}}",
+        ),
+        arms = arms,
+        sample_dir_arms = sample_dir_arms,
+    )
 }
 
-fn generate_day<S: AsRef<str>>(
-    day: u32,
-    year: i32,
-    year_path: &Path,
-    year_directory_name: S,
-    client: &Client,
-    indent: String,
-) -> Option<(String, String)> {
-    let input_file_name = format!("input{:02}.txt", day);
-    let input_path = year_path.join(&input_file_name);
-
-    if !input_path.is_file() {
-        let mut input_file = File::create(&input_path).unwrap();
-        let url = format!("https://adventofcode.com/{}/day/{}/input", year, day)
-            .parse::<Url>()
-            .unwrap();
-
-        println!("downolading input for {}/{}...", day, year);
-        let _ = writeln!(
-            input_file,
-            "{}",
-            client.get(url).send().unwrap().text().unwrap().trim()
-        );
-    }
-
-    let year_directory_name = year_directory_name.as_ref();
-    let source_path = Path::new(year_directory_name).join(format!("day{:02}.rs", day));
-    source_path.is_file().then(|| {
-        let sample_file_name = format!("sample{:02}.txt", day);
-        let sample_path = Path::new(year_directory_name).join(&sample_file_name);
+/// The module of the year and its entries in `YEARS`, `None` if it has no
+/// solutions yet.
+fn generate_year(years_dir: &Path, year: i32, indent: String) -> Option<(String, String)> {
+    let year_path = years_dir.join(format!("year{}", year));
+    let (mods, entries): (Vec<_>, Vec<_>) = (1..=25)
+        .filter_map(|day| generate_day(day, year, &year_path, format!("{}    ", indent)))
+        .unzip();
+    (!entries.is_empty()).then(|| {
         (
-            format!("pub mod day{:02};", day),
+            format!(
+                "pub mod year{year:04} {{\n{shared}{mods}}}\n",
+                year = year,
+                shared = generate_shared(&year_path),
+                mods = mods.join(""),
+            ),
             format!(
                 concat!(
-                    "{indent}map.insert({1}, (\n",
-                    "{indent}    Box::new(year{0:04}::day{1:02}::DayGen),\n",
-                    "{indent}    {2},\n",
-                    "{indent}    {3},\n",
-                    "{indent}));",
+                    "{indent}map.insert({year}, {{\n",
+                    "{indent}    let mut map = BTreeMap::new();\n",
+                    "{entries}\n",
+                    "{indent}    map\n",
+                    "{indent}}});",
                 ),
-                year,
-                day,
-                if input_path.is_file() {
-                    format!(
-                        "include_str!(\"./year{}/{}\").trim()",
-                        year, input_file_name
-                    )
-                } else {
-                    format!("\"no input for {day}\"", day = day)
-                },
-                if sample_path.is_file() {
-                    format!(
-                        "include_str!(\"./year{}/{}\").trim()",
-                        year, sample_file_name
-                    )
-                } else {
-                    format!("\"no example for {day}\"", day = day)
-                },
+                year = year,
+                entries = entries.join("\n"),
                 indent = indent,
             ),
         )
     })
 }
 
-fn client() -> Client {
-    let cookie = include_str!("cookie");
-
-    let url = "https://adventofcode.com/".parse::<Url>().unwrap();
-
-    let jar = Jar::default();
-    jar.add_cookie_str(cookie, &url);
-    Client::builder()
-        .cookie_provider(Arc::new(jar))
-        .build()
+/// Modules for the other files of a year, e.g. code shared between its days.
+fn generate_shared(year_path: &Path) -> String {
+    let mut names: Vec<_> = fs::read_dir(year_path)
         .unwrap()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|file_name| Some(file_name.strip_suffix(".rs")?.to_string()))
+        .filter(|name| {
+            let is_day = name
+                .strip_prefix("day")
+                .and_then(|day| day.split('_').next()?.parse::<u32>().ok())
+                .is_some();
+            // `mod.rs` and `day_mods.rs` were generated by earlier versions
+            !is_day && name != "mod" && name != "day_mods"
+        })
+        .collect();
+    names.sort();
+    names
+        .iter()
+        .map(|name| {
+            format!(
+                "    #[path = {}]\n    pub mod {};\n",
+                literal(&year_path.join(format!("{}.rs", name))),
+                name
+            )
+        })
+        .collect()
+}
+
+/// The modules of the variants of a day and its entry in `YEARS`, the
+/// variant in `day{day}.rs` is called `default`, the one in
+/// `day{day}_{variant}.rs` `variant`.
+fn generate_day(day: u32, year: i32, year_path: &Path, indent: String) -> Option<(String, String)> {
+    let prefix = format!("day{:02}", day);
+    let mut modules: Vec<_> = fs::read_dir(year_path)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|file_name| {
+            let module = file_name.strip_suffix(".rs")?;
+            let variant = match module.strip_prefix(&prefix)? {
+                "" => "default",
+                variant => variant.strip_prefix('_')?,
+            };
+            let valid = !variant.is_empty()
+                && variant
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            valid.then(|| (module.to_string(), variant.to_string()))
+        })
+        .collect();
+    modules.sort();
+    (!modules.is_empty()).then(|| {
+        let mods = modules
+            .iter()
+            .map(|(module, _)| {
+                format!(
+                    "    #[path = {}]\n    pub mod {};\n",
+                    literal(&year_path.join(format!("{}.rs", module))),
+                    module
+                )
+            })
+            .collect();
+        let variants = modules
+            .iter()
+            .map(|(module, variant)| {
+                format!(
+                    "({:?}, Box::new(year{:04}::{}::DayGen) as Box<dyn crate::DayGen + Sync>)",
+                    variant, year, module
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        (
+            mods,
+            format!(
+                "{indent}map.insert({}, crate::DayInfo::new(vec![{}]));",
+                day,
+                variants,
+                indent = indent,
+            ),
+        )
+    })
 }
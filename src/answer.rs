@@ -0,0 +1,159 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Letters drawn as a grid of glyphs, one entry per row.
+    Art(Vec<String>),
+    Unimplemented,
+}
+
+impl Answer {
+    /// Builds an [`Answer::Art`], trimming trailing whitespace from every row
+    /// and dropping empty rows at the top and bottom so that renderings
+    /// compare equal regardless of padding.
+    pub fn art<I, S>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut rows: Vec<String> = rows
+            .into_iter()
+            .map(|row| row.as_ref().trim_end().to_string())
+            .skip_while(String::is_empty)
+            .collect();
+        while matches!(rows.last(), Some(row) if row.is_empty()) {
+            rows.pop();
+        }
+        Answer::Art(rows)
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Art(_) => "art",
+            Answer::Unimplemented => "unimplemented",
+        }
+    }
+
+    /// The value without any decoration, art rows joined by `\n`.
+    ///
+    /// Parsing this with [`FromStr`] gives back an equal answer, except for
+    /// art of a single row, which comes back as text, and text that is
+    /// empty, contains newlines or reads as an integer.
+    pub fn to_machine(&self) -> String {
+        match self {
+            Answer::Integer(n) => n.to_string(),
+            Answer::Text(s) => s.clone(),
+            Answer::Art(rows) => rows.join("\n"),
+            Answer::Unimplemented => String::new(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{}", n),
+            Answer::Text(s) => f.write_str(s),
+            Answer::Art(rows) => rows.iter().try_for_each(|row| write!(f, "\n{}", row)),
+            Answer::Unimplemented => f.write_str("not implemented"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_matches(|c| c == '\n' || c == '\r');
+        Ok(if s.is_empty() {
+            Answer::Unimplemented
+        } else if s.contains('\n') {
+            Answer::art(s.lines())
+        } else {
+            match s.trim().parse::<i128>() {
+                // keep leading zeros and explicit signs as text
+                Ok(n) if n.to_string() == s.trim() => Answer::Integer(n),
+                _ => Answer::Text(s.to_string()),
+            }
+        })
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl TryFrom<&toml::Value> for Answer {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &toml::Value) -> Result<Self, Self::Error> {
+        match value {
+            toml::Value::Integer(n) => Ok(Answer::from(*n)),
+            toml::Value::String(s) => Ok(s.parse()?),
+            _ => Err(anyhow::anyhow!(
+                "expected an integer or a string, got {}",
+                value
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn machine_round_trip_test() {
+        for answer in [
+            Answer::from(-42i64),
+            Answer::from("0123"),
+            Answer::from("CJZLP"),
+            Answer::art(["", "#  # ", "#### ", "#  #", ""]),
+            Answer::Unimplemented,
+        ] {
+            assert_eq!(answer.to_machine().parse::<Answer>().unwrap(), answer);
+        }
+        for (answer, parsed) in [
+            (Answer::art(["#.#"]), Answer::from("#.#")),
+            (Answer::from(""), Answer::Unimplemented),
+            (Answer::from("ab\n"), Answer::from("ab")),
+            (Answer::from(" 42"), Answer::from(42)),
+        ] {
+            assert_eq!(answer.to_machine().parse::<Answer>().unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn art_padding_test() {
+        let a = Answer::art("\n\n█ █  \n █   \n".lines());
+        assert_eq!(a, Answer::Art(vec!["█ █".to_string(), " █".to_string()]));
+        assert_eq!(a.to_string(), "\n█ █\n █");
+    }
+}
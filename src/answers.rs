@@ -0,0 +1,102 @@
+use crate::Answer;
+use anyhow::{anyhow, Context, Result};
+use std::{collections::BTreeMap, fs, io, path::Path, str::FromStr};
+
+pub const FILE_NAME: &str = "answers.toml";
+
+/// Known correct answers, stored as
+///
+/// ```toml
+/// [2019.5]
+/// part1 = 9025675
+/// part2 = 11981754
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AnswerDb {
+    answers: BTreeMap<(i32, u32), [Option<Answer>; 2]>,
+}
+
+impl AnswerDb {
+    /// Loads the database at `path`, a missing file is an empty database.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .with_context(|| format!("invalid answers in {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("could not read {}", path.display())),
+        }
+    }
+
+    pub fn get(&self, year: i32, day: u32, part: u8) -> Option<&Answer> {
+        self.answers
+            .get(&(year, day))
+            .and_then(|parts| parts.get(usize::from(part).checked_sub(1)?)?.as_ref())
+    }
+}
+
+impl FromStr for AnswerDb {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: toml::Value = s.parse()?;
+        let years = value
+            .as_table()
+            .ok_or_else(|| anyhow!("expected a table of years"))?;
+        let mut answers = BTreeMap::new();
+        for (year, days) in years {
+            let year: i32 = year
+                .parse()
+                .with_context(|| format!("invalid year {:?}", year))?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| anyhow!("expected a table of days for {}", year))?;
+            for (day, parts) in days {
+                let day: u32 = day
+                    .parse()
+                    .with_context(|| format!("invalid day {:?}", day))?;
+                let part = |part: &str| {
+                    parts
+                        .get(part)
+                        .map(|value| {
+                            Answer::try_from(value).with_context(|| {
+                                format!("invalid {} of {} day {}", part, year, day)
+                            })
+                        })
+                        .transpose()
+                };
+                answers.insert((year, day), [part("part1")?, part("part2")?]);
+            }
+        }
+        Ok(Self { answers })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let db: AnswerDb = concat!(
+            "[2019.8]\n",
+            "part1 = 1820\n",
+            "part2 = \"\"\"\n",
+            "#### \n",
+            "#  # \n",
+            "\"\"\"\n",
+            "[2021.11]\n",
+            "part2 = 265\n",
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(db.get(2019, 8, 1), Some(&Answer::Integer(1820)));
+        assert_eq!(
+            db.get(2019, 8, 2),
+            Some(&Answer::Art(vec!["####".to_string(), "#  #".to_string()]))
+        );
+        assert_eq!(db.get(2021, 11, 1), None);
+        assert_eq!(db.get(2021, 11, 2), Some(&Answer::Integer(265)));
+        assert_eq!(db.get(2021, 12, 1), None);
+    }
+}
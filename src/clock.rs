@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
+use chrono_tz::{Tz, US::Eastern};
+use std::env;
+
+/// The current time in the timezone of AoC. `AOC_NOW` overrides it with an
+/// RFC 3339 time like `2021-12-05T00:00:00-05:00`.
+pub fn now() -> Result<DateTime<Tz>> {
+    match env::var("AOC_NOW") {
+        Ok(now) => parse(&now).with_context(|| format!("invalid AOC_NOW {:?}", now)),
+        Err(_) => Ok(Utc::now().with_timezone(&Eastern)),
+    }
+}
+
+fn parse(time: &str) -> Result<DateTime<Tz>> {
+    Ok(DateTime::<FixedOffset>::parse_from_rfc3339(time.trim())?.with_timezone(&Eastern))
+}
+
+/// Whether the puzzle of the day is unlocked at `now`, which happens at
+/// midnight in the timezone of AoC, so `now` has to be in that timezone.
+pub fn released(year: i32, day: u32, now: DateTime<Tz>) -> bool {
+    (1..=25).contains(&day)
+        && (now.year() > year || now.year() == year && now.month() == 12 && now.day() >= day)
+}
+
+/// The puzzle unlocked today, `None` outside of the first 25 days of December.
+pub fn today(now: DateTime<Tz>) -> Option<(i32, u32)> {
+    (now.month() == 12 && now.day() <= 25).then(|| (now.year(), now.day()))
+}
+
+/// The latest year with puzzles at `now`, the current one from December on.
+pub fn event_year(now: DateTime<Tz>) -> i32 {
+    if now.month() == 12 {
+        now.year()
+    } else {
+        now.year() - 1
+    }
+}
+
+/// When the puzzle of the day unlocks, midnight in the timezone of AoC.
+pub fn unlock(year: i32, day: u32) -> Option<DateTime<Tz>> {
+    let date = NaiveDate::from_ymd_opt(year, 12, day).filter(|_| (1..=25).contains(&day))?;
+    Eastern
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .single()
+}
+
+/// The next puzzle to unlock after `now` and when that happens.
+pub fn next_unlock(now: DateTime<Tz>) -> (i32, u32, DateTime<Tz>) {
+    let (year, day) = match today(now) {
+        Some((year, 25)) => (year + 1, 1),
+        Some((year, day)) => (year, day + 1),
+        None => (event_year(now) + 1, 1),
+    };
+    (year, day, unlock(year, day).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_test() {
+        let at = |time: &str| parse(time).unwrap();
+        assert!(released(2021, 5, at("2021-12-05T00:00:00-05:00")));
+        assert!(!released(2021, 5, at("2021-12-05T04:59:59Z")));
+        assert!(!released(2021, 6, at("2021-12-05T23:00:00-05:00")));
+        assert!(released(2020, 25, at("2021-01-01T00:00:00-05:00")));
+        assert!(!released(2021, 26, at("2021-12-31T00:00:00-05:00")));
+        assert!(parse("tomorrow").is_err());
+    }
+
+    #[test]
+    fn next_unlock_test() {
+        let at = |time: &str| parse(time).unwrap();
+        assert_eq!(
+            next_unlock(at("2021-12-05T13:00:00-05:00")),
+            (2021, 6, at("2021-12-06T00:00:00-05:00"))
+        );
+        assert_eq!(
+            next_unlock(at("2021-12-25T00:00:00-05:00")),
+            (2022, 1, at("2022-12-01T00:00:00-05:00"))
+        );
+        assert_eq!(
+            next_unlock(at("2022-10-18T12:00:00Z")),
+            (2022, 1, at("2022-12-01T00:00:00-05:00"))
+        );
+        assert_eq!(today(at("2021-12-26T00:00:00-05:00")), None);
+        assert_eq!(event_year(at("2022-01-01T00:00:00-05:00")), 2021);
+        assert_eq!(unlock(2021, 26), None);
+    }
+}
@@ -0,0 +1,413 @@
+use crate::inputs::{self, Kind};
+use anyhow::{anyhow, Context, Result};
+use reqwest::{blocking, header, redirect, StatusCode};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the tool to the AoC servers as they ask for.
+pub const USER_AGENT: &str = concat!(
+    "aoc-rs/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/cafce25/aoc-rs)"
+);
+
+/// Talks to the AoC website, or anything else serving the same paths.
+pub struct Client {
+    base_url: String,
+    cookie: String,
+    http: blocking::Client,
+    /// how often a request is repeated after a network or server error
+    pub retries: u32,
+    /// the wait before the first retry, it doubles with every further one
+    pub backoff: Duration,
+    /// the least time between the start of two requests
+    pub min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, cookie: &str) -> Result<Self> {
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            cookie: cookie.to_string(),
+            http: blocking::Client::builder()
+                .user_agent(USER_AGENT)
+                .redirect(redirect::Policy::none())
+                .timeout(Duration::from_secs(30))
+                .build()?,
+            retries: 3,
+            backoff: Duration::from_secs(1),
+            min_interval: Duration::from_secs(1),
+            last_request: Mutex::new(None),
+        })
+    }
+
+    /// Downloads the input of the given day.
+    pub fn input(&self, year: i32, day: u32) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let (status, body) = self.get(&url)?;
+        if !status.is_success() {
+            return Err(anyhow!(
+                "{} answered with {}: {}",
+                url,
+                status,
+                body.lines().next().unwrap_or_default().trim()
+            ));
+        }
+        check_input(&body).with_context(|| format!("{} didn't answer with an input", url))?;
+        Ok(body)
+    }
+
+    /// Reads the title of the given day from its puzzle page.
+    pub fn title(&self, year: i32, day: u32) -> Result<String> {
+        let url = format!("{}/{}/day/{}", self.base_url, year, day);
+        let (status, body) = self.get(&url)?;
+        if !status.is_success() {
+            return Err(anyhow!("{} answered with {}", url, status));
+        }
+        parse_title(&body).ok_or_else(|| anyhow!("{} has no puzzle title", url))
+    }
+
+    /// Submits the answer of a part, returns the page that tells whether it
+    /// was right. It is never retried as that could count as another guess.
+    pub fn answer(&self, year: i32, day: u32, part: u8, answer: &str) -> Result<String> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        self.throttle();
+        let response = self
+            .http
+            .post(&url)
+            .header(header::COOKIE, &self.cookie)
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+            .send()
+            .with_context(|| format!("could not post to {}", url))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .with_context(|| format!("could not read the answer of {}", url))?;
+        if !status.is_success() {
+            return Err(anyhow!(
+                "{} answered with {}: {}",
+                url,
+                status,
+                body.lines().next().unwrap_or_default().trim()
+            ));
+        }
+        Ok(body)
+    }
+
+    /// Whether the session is logged in, i.e. may see the settings page
+    /// instead of being redirected to the login.
+    pub fn logged_in(&self) -> Result<bool> {
+        let url = format!("{}/settings", self.base_url);
+        let (status, _) = self.get(&url)?;
+        if status.is_success() {
+            Ok(true)
+        } else if status.is_redirection() || status.is_client_error() {
+            Ok(false)
+        } else {
+            Err(anyhow!("{} answered with {}", url, status))
+        }
+    }
+
+    /// Requests `url`, retrying network and server errors with exponential
+    /// backoff.
+    fn get(&self, url: &str) -> Result<(StatusCode, String)> {
+        let mut attempt = 0;
+        loop {
+            match self.try_get(url) {
+                Err(_) if attempt < self.retries => {
+                    thread::sleep(self.backoff * 2u32.pow(attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Requests `url` once, every error is worth retrying.
+    fn try_get(&self, url: &str) -> Result<(StatusCode, String)> {
+        self.throttle();
+        let response = self
+            .http
+            .get(url)
+            .header(header::COOKIE, &self.cookie)
+            .send()
+            .with_context(|| format!("could not request {}", url))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .with_context(|| format!("could not read the answer of {}", url))?;
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(anyhow!(
+                "{} answered with {}: {}",
+                url,
+                status,
+                body.lines().next().unwrap_or_default().trim()
+            ));
+        }
+        Ok((status, body))
+    }
+
+    /// Waits until `min_interval` has passed since the last request.
+    fn throttle(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+        if let Some(last_request) = *last_request {
+            thread::sleep(self.min_interval.saturating_sub(last_request.elapsed()));
+        }
+        *last_request = Some(Instant::now());
+    }
+}
+
+/// The title in the `<h2>--- Day 1: Sonar Sweep ---</h2>` heading of a
+/// puzzle page.
+fn parse_title(page: &str) -> Option<String> {
+    let heading = page.split("<h2>").nth(1)?.split("</h2>").next()?;
+    let title = heading
+        .trim()
+        .strip_prefix("---")?
+        .strip_suffix("---")?
+        .split_once(':')?
+        .1
+        .trim();
+    let title = title
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    (!title.is_empty()).then_some(title)
+}
+
+/// Rejects the pages AoC serves instead of an input, e.g. when the session
+/// expired.
+fn check_input(body: &str) -> Result<()> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Err(anyhow!("the answer is empty"));
+    }
+    if body.contains("Please log in") {
+        return Err(anyhow!(
+            "the session is not logged in, is the cookie still valid?"
+        ));
+    }
+    if body.contains("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(anyhow!("the puzzle is not unlocked yet"));
+    }
+    // inputs can start with `<` too, e.g. the brackets of 2021 day 10
+    let start = body.get(..14).unwrap_or(body).to_ascii_lowercase();
+    if start.starts_with("<!doctype") || start.starts_with("<html") {
+        return Err(anyhow!("the answer is a web page"));
+    }
+    Ok(())
+}
+
+/// Downloads the input of the given day into `dir` unless it is already
+/// there, returns where the input is and whether it was downloaded.
+pub fn fetch(client: &Client, dir: &Path, year: i32, day: u32) -> Result<(PathBuf, bool)> {
+    let path = inputs::path(dir, year, day, Kind::Input);
+    if path.is_file() {
+        return Ok((path, false));
+    }
+    let input = client.input(year, day)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("could not create {}", parent.display()))?;
+    }
+    // write next to the input and rename so an interrupted download never
+    // leaves a partial input behind
+    let part = path.with_extension("txt.part");
+    fs::write(&part, input.trim_end().to_string() + "\n")
+        .and_then(|()| fs::rename(&part, &path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&part);
+        })
+        .with_context(|| format!("could not write {}", path.display()))?;
+    Ok((path, true))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Answers one request after another with `responses` on a local port,
+    /// the handle returns the head of every request followed by its body if
+    /// it has one.
+    pub(crate) fn serve(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, thread::JoinHandle<Vec<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = Vec::new();
+                let mut line = String::new();
+                let mut length = 0;
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push(line.trim_end().to_string());
+                    line.clear();
+                }
+                if length > 0 {
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    request.push(String::from_utf8(body).unwrap());
+                }
+                requests.push(request);
+                write!(
+                    &stream,
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (base_url, server)
+    }
+
+    pub(crate) fn client(base_url: &str) -> Client {
+        let mut client = Client::new(base_url, "session=abc").unwrap();
+        client.backoff = Duration::from_millis(10);
+        client.min_interval = Duration::ZERO;
+        client
+    }
+
+    pub(crate) fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn fetch_test() {
+        let (base_url, server) = serve(vec![(200, "1\n2\n3\n")]);
+        let dir = temp_dir("fetch-test");
+        let client = client(&base_url);
+        let (path, downloaded) = fetch(&client, &dir, 2021, 1).unwrap();
+        assert!(downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n3\n");
+        let (_, downloaded) = fetch(&client, &dir, 2021, 1).unwrap();
+        assert!(!downloaded);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0][0], "GET /2021/day/1/input HTTP/1.1");
+        assert!(requests[0].iter().any(|line| line == "cookie: session=abc"));
+        assert!(requests[0]
+            .iter()
+            .any(|line| line == &format!("user-agent: {}", USER_AGENT)));
+    }
+
+    #[test]
+    fn title_test() {
+        let (base_url, server) = serve(vec![
+            (
+                200,
+                "<main><article><h2>--- Day 2: Dive! ---</h2><p>Now</p></article></main>",
+            ),
+            (200, "<main><h2>Sign in</h2></main>"),
+        ]);
+        let client = client(&base_url);
+        assert_eq!(client.title(2021, 2).unwrap(), "Dive!");
+        assert!(client.title(2021, 3).is_err());
+        assert_eq!(server.join().unwrap()[0][0], "GET /2021/day/2 HTTP/1.1");
+        assert_eq!(
+            parse_title("<h2>--- Day 8: I Heard You Like &quot;Registers&quot; ---</h2>")
+                .as_deref(),
+            Some("I Heard You Like \"Registers\"")
+        );
+    }
+
+    #[test]
+    fn retry_test() {
+        let (base_url, server) = serve(vec![(503, "busy"), (500, "oops"), (200, "42")]);
+        let start = Instant::now();
+        assert_eq!(client(&base_url).input(2021, 1).unwrap(), "42");
+        assert!(start.elapsed() >= Duration::from_millis(10 + 20));
+        assert_eq!(server.join().unwrap().len(), 3);
+
+        let (base_url, server) = serve(vec![(503, "busy"), (503, "busy")]);
+        let mut client = client(&base_url);
+        client.retries = 1;
+        assert!(client.input(2021, 1).is_err());
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn error_page_test() {
+        let (base_url, server) = serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (
+                200,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+            (200, "<!DOCTYPE html>\n<html></html>"),
+            (200, ""),
+        ]);
+        let dir = temp_dir("error-page-test");
+        let client = client(&base_url);
+        for _ in 0..4 {
+            assert!(fetch(&client, &dir, 2021, 1).is_err());
+            assert!(!inputs::path(&dir, 2021, 1, Kind::Input).exists());
+        }
+        // none of them is retried
+        assert_eq!(server.join().unwrap().len(), 4);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn bracket_input_test() {
+        let (base_url, server) = serve(vec![(200, "<{([{{}}[<[[[<>{}]]]>[]]\n[(()[<>])]\n")]);
+        let dir = temp_dir("bracket-input-test");
+        let (path, _) = fetch(&client(&base_url), &dir, 2021, 10).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "<{([{{}}[<[[[<>{}]]]>[]]\n[(()[<>])]\n"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+        server.join().unwrap();
+        assert!(check_input("<HTML><body>oops</body></HTML>").is_err());
+    }
+
+    #[test]
+    fn logged_in_test() {
+        let (base_url, server) = serve(vec![(200, "settings"), (302, ""), (400, "")]);
+        let client = client(&base_url);
+        assert!(client.logged_in().unwrap());
+        assert!(!client.logged_in().unwrap());
+        assert!(!client.logged_in().unwrap());
+        assert_eq!(server.join().unwrap()[0][0], "GET /settings HTTP/1.1");
+    }
+
+    #[test]
+    fn throttle_test() {
+        let (base_url, server) = serve(vec![(200, "1"), (200, "2")]);
+        let mut client = client(&base_url);
+        client.min_interval = Duration::from_millis(50);
+        let start = Instant::now();
+        client.input(2021, 1).unwrap();
+        client.input(2021, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        server.join().unwrap();
+    }
+}
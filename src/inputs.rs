@@ -0,0 +1,204 @@
+use crate::Answer;
+use anyhow::{anyhow, Context, Result};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Which text of a day to load.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Kind {
+    Input,
+    Sample,
+}
+
+impl Kind {
+    pub fn file_name(self, day: u32) -> String {
+        match self {
+            Kind::Input => format!("input{:02}.txt", day),
+            Kind::Sample => format!("sample{:02}.txt", day),
+        }
+    }
+}
+
+/// The input directory from `AOC_INPUT_DIR`, falling back to
+/// [`crate_dir`].
+pub fn default_dir() -> PathBuf {
+    env::var_os("AOC_INPUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(crate_dir)
+}
+
+/// `src/years` of this crate, where the samples are checked in.
+pub fn crate_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("src")
+        .join("years")
+}
+
+/// Where the text of `kind` for the given day lives below `dir`,
+/// i.e. `{dir}/year{year}/input{day}.txt`.
+pub fn path(dir: &Path, year: i32, day: u32, kind: Kind) -> PathBuf {
+    dir.join(format!("year{}", year)).join(kind.file_name(day))
+}
+
+/// Reads the text of `kind` for the given day from `dir`.
+///
+/// With the `embed-inputs` feature a copy compiled into the binary is used
+/// when the file does not exist.
+pub fn load(dir: &Path, year: i32, day: u32, kind: Kind) -> Result<String> {
+    let path = path(dir, year, day, kind);
+    #[cfg(feature = "embed-inputs")]
+    if !path.exists() {
+        if let Some(text) = crate::years::embedded(year, day, kind) {
+            return Ok(text.trim().to_string());
+        }
+    }
+    read(&path)
+}
+
+pub fn input(year: i32, day: u32) -> Result<String> {
+    load(&default_dir(), year, day, Kind::Input)
+}
+
+pub fn sample(year: i32, day: u32) -> Result<String> {
+    load(&default_dir(), year, day, Kind::Sample)
+}
+
+/// Reads the file at `path`, or stdin if `path` is `-`.
+pub fn read(path: &Path) -> Result<String> {
+    let text = if path == Path::new("-") {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("could not read stdin")?;
+        text
+    } else {
+        fs::read_to_string(path).with_context(|| format!("could not read {}", path.display()))?
+    };
+    Ok(text.trim().to_string())
+}
+
+/// An example from the puzzle text and the answers it is expected to give.
+#[derive(Clone, Debug)]
+pub struct Sample {
+    pub name: String,
+    pub text: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// The sidecar in a sample directory holding the expected answers, e.g.
+///
+/// ```toml
+/// [small]
+/// part1 = 10
+/// part2 = 36
+/// ```
+pub const EXPECTED_FILE: &str = "expected.toml";
+
+/// Loads every sample of a day: the `sample{day}.txt` file and each
+/// `sample{day}/{name}.txt` with its answers from [`EXPECTED_FILE`].
+pub fn samples(dir: &Path, year: i32, day: u32) -> Result<Vec<Sample>> {
+    let mut files = sample_dir(dir, year, day)?;
+    let expected = match files.iter().position(|(name, _)| name == EXPECTED_FILE) {
+        Some(i) => {
+            let (_, text) = files.remove(i);
+            text.parse::<toml::Value>()
+                .with_context(|| format!("invalid {} for {} day {}", EXPECTED_FILE, year, day))?
+        }
+        None => toml::Value::Table(Default::default()),
+    };
+
+    let mut samples = Vec::new();
+    if let Ok(text) = load(dir, year, day, Kind::Sample) {
+        samples.push(Sample {
+            name: format!("sample{:02}", day),
+            text,
+            part1: None,
+            part2: None,
+        });
+    }
+    for (file_name, text) in files {
+        let name = match file_name.strip_suffix(".txt") {
+            Some(name) => name,
+            None => continue,
+        };
+        let part = |part: &str| {
+            expected
+                .get(name)
+                .and_then(|answers| answers.get(part))
+                .map(|value| {
+                    Answer::try_from(value)
+                        .with_context(|| format!("invalid {} answer for sample {}", part, name))
+                })
+                .transpose()
+        };
+        samples.push(Sample {
+            name: name.to_string(),
+            text: text.trim().to_string(),
+            part1: part("part1")?,
+            part2: part("part2")?,
+        });
+    }
+    if samples.is_empty() {
+        return Err(anyhow!("no samples for {} day {}", year, day));
+    }
+    Ok(samples)
+}
+
+/// Whether the given day has any samples, [`samples`] fails without them.
+pub fn has_samples(dir: &Path, year: i32, day: u32) -> bool {
+    load(dir, year, day, Kind::Sample).is_ok()
+        || matches!(sample_dir(dir, year, day), Ok(files) if !files.is_empty())
+}
+
+/// The files in `sample{day}/` sorted by name.
+fn sample_dir(dir: &Path, year: i32, day: u32) -> Result<Vec<(String, String)>> {
+    let path = dir
+        .join(format!("year{}", year))
+        .join(format!("sample{:02}", day));
+    if !path.is_dir() {
+        #[cfg(feature = "embed-inputs")]
+        return Ok(crate::years::embedded_sample_dir(year, day)
+            .iter()
+            .map(|(name, text)| (name.to_string(), text.to_string()))
+            .collect());
+        #[cfg(not(feature = "embed-inputs"))]
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in
+        fs::read_dir(&path).with_context(|| format!("could not read {}", path.display()))?
+    {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        if let Ok(name) = entry.file_name().into_string() {
+            let text = fs::read_to_string(entry.path())
+                .with_context(|| format!("could not read {}", entry.path().display()))?;
+            files.push((name, text));
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_test() {
+        let samples = samples(&crate_dir(), 2021, 12).unwrap();
+        let names: Vec<_> = samples.iter().map(|s| &s.name[..]).collect();
+        assert_eq!(names, ["larger", "medium", "small"]);
+        assert_eq!(samples[2].text.lines().count(), 7);
+        assert_eq!(samples[2].part1, Some(Answer::Integer(10)));
+        assert_eq!(samples[2].part2, Some(Answer::Integer(36)));
+        assert!(has_samples(&crate_dir(), 2021, 12));
+        assert!(!has_samples(&crate_dir(), 2014, 1));
+    }
+}
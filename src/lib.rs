@@ -1,13 +1,66 @@
 #![feature(iter_intersperse, iter_advance_by, mixed_integer_ops, drain_filter, test)]
+pub mod answer;
+pub mod answers;
+pub mod clock;
+pub mod fetch;
+pub mod inputs;
+pub mod progress;
+pub mod scaffold;
+pub mod selection;
+pub mod session;
+pub mod submit;
+pub mod timing;
+pub mod titles;
 pub mod years;
 
 pub const MIN_YEAR: i32 = 2015;
 pub use years::YEARS;
 
+pub use answer::Answer;
+
+use anyhow::Result;
+
 pub trait DayGen {
-    fn input<'a>(&'a self, input: &'a str) -> Box<dyn Day + 'a>;
+    fn input<'a>(&'a self, input: &'a str) -> Result<Box<dyn Day + 'a>>;
 }
 pub trait Day {
-    fn part1(&self) -> String;
-    fn part2(&self) -> String;
+    fn part1(&self) -> Result<Answer>;
+    fn part2(&self) -> Result<Answer>;
+}
+
+/// The name of the solution in `day{day}.rs`, other variants of a day live in
+/// `day{day}_{variant}.rs`.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// The solutions of a day.
+pub struct DayInfo {
+    /// sorted by name, except for the default coming first
+    variants: Vec<(&'static str, Box<dyn DayGen + Sync>)>,
+}
+
+impl DayInfo {
+    pub fn new(mut variants: Vec<(&'static str, Box<dyn DayGen + Sync>)>) -> Self {
+        assert!(!variants.is_empty(), "a day needs a solution");
+        variants.sort_by_key(|(name, _)| (*name != DEFAULT_VARIANT, *name));
+        Self { variants }
+    }
+
+    /// The default variant, or the first one if there is none.
+    pub fn main(&self) -> (&'static str, &(dyn DayGen + Sync)) {
+        let (name, day_gen) = &self.variants[0];
+        (name, day_gen.as_ref())
+    }
+
+    pub fn variant(&self, name: &str) -> Option<&(dyn DayGen + Sync)> {
+        self.variants
+            .iter()
+            .find(|(variant, _)| *variant == name)
+            .map(|(_, day_gen)| day_gen.as_ref())
+    }
+
+    pub fn variants(&self) -> impl Iterator<Item = (&'static str, &(dyn DayGen + Sync))> {
+        self.variants
+            .iter()
+            .map(|(name, day_gen)| (*name, day_gen.as_ref()))
+    }
 }
@@ -1,21 +1,192 @@
-use chrono::{Datelike, Utc};
-use std::str::FromStr;
+use anyhow::Context;
+use aoc::{
+    answers::{self, AnswerDb},
+    clock, fetch,
+    inputs::{self, Kind},
+    progress::{Status, YearProgress},
+    scaffold,
+    selection::List,
+    session,
+    submit::{self, Verdict},
+    timing::{self, Stats},
+    titles::{self, Titles},
+    years::year2019::{
+        debugger::Debugger,
+        intcode::{self, Intcode, Machine},
+    },
+    Answer,
+};
+use std::{
+    collections::BTreeMap,
+    io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{self, AtomicUsize},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 use structopt::StructOpt;
-// the timezone used by AoC
-use chrono_tz::US::Eastern;
 
 #[derive(Debug, StructOpt)]
 struct Opt {
+    /// the days to run, e.g. `5`, `1-10` or `5,7,12`
     #[structopt(short, long)]
-    day: Option<u32>,
+    day: Option<List<u32>>,
+    /// the years to run, e.g. `2021`, `21` or `2019,2021`
     #[structopt(short, long)]
-    year: Option<i32>,
+    year: Option<List<i32>>,
     #[structopt(short, long)]
     sample: bool,
     #[structopt(short, long)]
     all: bool,
+    /// runs all days of all years
+    #[structopt(long, conflicts_with = "year")]
+    all_years: bool,
     #[structopt(short, long, default_value = "both")]
     part: Part,
+    /// directory containing the `year{year}/input{day}.txt` files
+    #[structopt(long, env = "AOC_INPUT_DIR", parse(from_os_str))]
+    input_dir: Option<PathBuf>,
+    /// read the input of the day from this file, `-` reads from stdin
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        conflicts_with_all = &["sample", "all", "all-years"]
+    )]
+    input: Option<PathBuf>,
+    /// how many days are solved at the same time, 0 uses all cores
+    #[structopt(short, long, default_value = "1")]
+    jobs: usize,
+    /// which variant of the days to run, defaults to `default`
+    #[structopt(long)]
+    variant: Option<String>,
+    /// how the solutions are printed: text (the default), json or tsv
+    #[structopt(short, long)]
+    format: Option<Format>,
+    #[structopt(subcommand)]
+    cmd: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Checks the answers of all solutions against `answers.toml` in the input directory
+    Verify {
+        #[structopt(short, long)]
+        year: Option<List<i32>>,
+        #[structopt(short, long)]
+        day: Option<List<u32>>,
+    },
+    /// Times parsing and both parts of the solutions
+    Bench {
+        #[structopt(short, long)]
+        year: Option<List<i32>>,
+        #[structopt(short, long)]
+        day: Option<List<u32>>,
+        /// how often each step is run
+        #[structopt(short = "n", long, default_value = "10")]
+        iterations: usize,
+    },
+    /// Downloads the missing inputs of the released days into the input directory
+    Fetch {
+        #[structopt(short, long)]
+        year: Option<List<i32>>,
+        #[structopt(short, long)]
+        day: Option<List<u32>>,
+        /// where the inputs are downloaded from, defaults to https://adventofcode.com
+        #[structopt(long, env = "AOC_BASE_URL")]
+        base_url: Option<String>,
+    },
+    /// Solves a part and submits its answer, keeping every guess in `guesses.toml`
+    /// in the input directory
+    Submit {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        #[structopt(short, long)]
+        day: Option<u32>,
+        /// 1 or 2
+        #[structopt(short, long)]
+        part: u8,
+        /// submits this answer instead of solving the part
+        #[structopt(long)]
+        answer: Option<String>,
+        /// where the answer is submitted, defaults to https://adventofcode.com
+        #[structopt(long, env = "AOC_BASE_URL")]
+        base_url: Option<String>,
+    },
+    /// Manages the AoC session used to download inputs
+    Auth(AuthCommand),
+    /// Tools for the Intcode programs of 2019
+    Intcode(IntcodeCommand),
+    /// Lists the implemented days with their titles, variants, inputs and known answers
+    Status {
+        #[structopt(short, long)]
+        year: Option<List<i32>>,
+        #[structopt(short, long)]
+        day: Option<List<u32>>,
+    },
+    /// Counts down to the next puzzle unlock
+    Next,
+    /// Shows which parts are implemented and have a known answer in a calendar per year
+    Progress {
+        #[structopt(short, long)]
+        year: Option<List<i32>>,
+        /// text, json or tsv
+        #[structopt(short, long, default_value = "text")]
+        format: Format,
+    },
+    /// Creates the solution of a day from a template
+    New {
+        #[structopt(short, long)]
+        year: Option<i32>,
+        #[structopt(short, long)]
+        day: Option<u32>,
+        /// the template to use instead of the built-in one, `{{year}}`, `{{day}}`
+        /// and `{{day:02}}` are replaced
+        #[structopt(long, env = "AOC_TEMPLATE", parse(from_os_str))]
+        template: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum AuthCommand {
+    /// Checks whether the session is logged in
+    Check {
+        /// the AoC server to ask, defaults to https://adventofcode.com
+        #[structopt(long, env = "AOC_BASE_URL")]
+        base_url: Option<String>,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum IntcodeCommand {
+    /// Prints the instructions of a program with their addresses and words,
+    /// `>` marks jump targets
+    Disasm {
+        /// the program, `-` reads it from stdin
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+        /// prints only the instructions, so `aoc intcode asm` turns them back into the program
+        #[structopt(long)]
+        source: bool,
+    },
+    /// Runs a program in an interactive debugger, `help` lists its commands
+    Debug {
+        /// the program, `-` reads it from stdin
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
+    /// Assembles a program written in the syntax of the disassembler, with
+    /// `label:`s, `data` and `;` comments
+    Asm {
+        /// the assembly, `-` reads it from stdin
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
@@ -45,63 +216,1183 @@ impl FromStr for Part {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Format {
+    Text,
+    /// one object per part and line
+    Json,
+    /// a header and one row per part
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json or tsv",
+                s
+            )),
+        }
+    }
+}
+
+/// Allows years to be given as `21` instead of `2021`.
+fn full_year(year: i32) -> i32 {
+    if year < 2000 {
+        year + 2000
+    } else {
+        year
+    }
+}
+
 fn main() {
     let opt = Opt::from_args();
-    let aoc_time = Utc::now().with_timezone(&Eastern);
-    let year = match opt.year {
-        Some(y) => {
-            if y < 2000 {
-                y + 2000
-            } else {
-                y
-            }
+    let aoc_time = match clock::now() {
+        Ok(now) => now,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            std::process::exit(1);
         }
-        None => aoc_time.year(),
     };
-    if !opt.all {
-        // run a single challenge
-        // EST/UTC-5
-        let day = match opt.day {
-            Some(d) => d,
-            None => aoc_time.day(),
+    let input_dir = opt.input_dir.unwrap_or_else(inputs::default_dir);
+    // today's puzzle during the event, otherwise the next one to unlock
+    let (next_year, next_day) = clock::today(aoc_time).unwrap_or_else(|| {
+        let (year, day, _) = clock::next_unlock(aoc_time);
+        (year, day)
+    });
+    if let Some(cmd) = opt.cmd {
+        let result = match cmd {
+            Command::Verify { year, day } => verify(
+                &input_dir,
+                year.map(|y| y.map(full_year)).as_ref(),
+                day.as_ref(),
+            ),
+            Command::Bench {
+                year,
+                day,
+                iterations,
+            } => bench(
+                &input_dir,
+                year.map(|y| y.map(full_year)).as_ref(),
+                day.as_ref(),
+                iterations,
+            ),
+            Command::Fetch {
+                year,
+                day,
+                base_url,
+            } => fetch_inputs(
+                &input_dir,
+                year.map(|y| y.map(full_year)).as_ref(),
+                day.as_ref(),
+                base_url.as_deref().unwrap_or(fetch::DEFAULT_BASE_URL),
+            ),
+            Command::New {
+                year,
+                day,
+                template,
+            } => new_day(
+                &input_dir,
+                year.map_or(next_year, full_year),
+                day.unwrap_or(next_day),
+                template.as_deref(),
+            ),
+            Command::Submit {
+                year,
+                day,
+                part,
+                answer,
+                base_url,
+            } => submit_answer(
+                &input_dir,
+                year.map_or(next_year, full_year),
+                day.unwrap_or(next_day),
+                part,
+                answer.as_deref(),
+                base_url.as_deref().unwrap_or(fetch::DEFAULT_BASE_URL),
+            ),
+            Command::Auth(AuthCommand::Check { base_url }) => {
+                check_session(base_url.as_deref().unwrap_or(fetch::DEFAULT_BASE_URL))
+            }
+            Command::Intcode(IntcodeCommand::Disasm { file, source }) => disassemble(&file, source),
+            Command::Intcode(IntcodeCommand::Asm { file }) => assemble(&file),
+            Command::Intcode(IntcodeCommand::Debug { file }) => debug(&file),
+            Command::Status { year, day } => status(
+                &input_dir,
+                year.map(|y| y.map(full_year)).as_ref(),
+                day.as_ref(),
+            ),
+            Command::Next => next_unlock(aoc_time),
+            Command::Progress { year, format } => progress(
+                &input_dir,
+                year.map(|y| y.map(full_year)).as_ref(),
+                clock::event_year(aoc_time),
+                format,
+            ),
         };
-        let (day_gen, day_input, day_sample) = &aoc::YEARS[&year][&day];
-        run_day(
-            day_gen.as_ref(),
-            day_input,
-            day_sample,
-            day,
-            opt.sample,
-            opt.part,
-        )
+        match result {
+            Ok(true) => {}
+            Ok(false) => std::process::exit(1),
+            Err(e) => {
+                eprintln!("error: {:#}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let years = match opt.year {
+        _ if opt.all_years => None,
+        Some(years) => Some(years.map(full_year)),
+        None => Some(clock::event_year(aoc_time).into()),
+    };
+    let days = match opt.day {
+        Some(days) => Some(days),
+        None if opt.all || opt.all_years => None,
+        // run the current challenge
+        None => match clock::today(aoc_time) {
+            Some((_, day)) => Some(day.into()),
+            None => {
+                eprintln!(
+                    "error: no puzzle unlocked today, select days with -d or all of them with -a, \
+                     `aoc next` tells when the next one unlocks"
+                );
+                std::process::exit(1);
+            }
+        },
+    };
+    let days = match select(years.as_ref(), days.as_ref())
+        .and_then(|days| variants(&days, opt.variant.as_deref()))
+    {
+        Ok(days) if opt.input.is_some() && days.len() > 1 => {
+            eprintln!(
+                "error: --input needs a single day, but {} are selected",
+                days.len()
+            );
+            std::process::exit(1);
+        }
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            std::process::exit(1);
+        }
+    };
+    let mut failed = false;
+    if opt.sample {
+        if opt.format.is_some() {
+            eprintln!("error: --format can't be used with --sample");
+            std::process::exit(1);
+        }
+        for &(year, day, _, day_gen) in &days {
+            if let Err(e) = run_samples(day_gen, &input_dir, year, day, opt.part) {
+                eprintln!("error in {} day {}: {:#}", year, day, e);
+                failed = true;
+            }
+        }
     } else {
-        for (day, (day_gen, day_input, day_sample)) in aoc::YEARS[&year].iter() {
-            run_day(
-                day_gen.as_ref(),
-                day_input,
-                day_sample,
-                *day,
-                opt.sample,
-                opt.part,
-            )
+        let source = match &opt.input {
+            Some(path) => Source::Path(path),
+            None => Source::Dir(&input_dir),
+        };
+        let jobs = match opt.jobs {
+            0 => thread::available_parallelism().map_or(1, usize::from),
+            jobs => jobs,
+        };
+        let format = opt.format.unwrap_or(Format::Text);
+        if format == Format::Tsv {
+            println!("year\tday\tvariant\tpart\tanswer\tduration\terror");
         }
+        for_each_ordered(
+            jobs,
+            &days,
+            |&(year, day, variant, day_gen)| solve(day_gen, source, year, day, variant, opt.part),
+            |run| {
+                failed |= !match format {
+                    Format::Text => print_run(&run),
+                    Format::Json => print_records(&records(opt.part, &run), json_line),
+                    Format::Tsv => print_records(&records(opt.part, &run), tsv_row),
+                }
+            },
+        );
     }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Where the text fed into a day comes from.
+#[derive(Copy, Clone, Debug)]
+enum Source<'a> {
+    /// the input in the input directory
+    Dir(&'a Path),
+    /// a file given on the command line, `-` is stdin
+    Path(&'a Path),
+}
+
+/// The outcome of solving one day.
+struct DayRun {
+    year: i32,
+    day: u32,
+    variant: &'static str,
+    /// the time parsing took and the parts, or why the input couldn't be parsed
+    result: anyhow::Result<(Duration, Vec<PartRun>)>,
 }
 
-fn run_day<'a>(
-    day: &'a (dyn aoc::DayGen + Sync),
-    input_str: &'a str,
-    sample_str: &'a str,
+struct PartRun {
+    part: u8,
+    answer: anyhow::Result<Answer>,
+    duration: Duration,
+}
+
+fn solve(
+    day_gen: &(dyn aoc::DayGen + Sync),
+    source: Source,
+    year: i32,
+    day: u32,
+    variant: &'static str,
+    part: Part,
+) -> DayRun {
+    let result = catch_panic(|| {
+        let text = match source {
+            Source::Dir(input_dir) => inputs::load(input_dir, year, day, Kind::Input)?,
+            Source::Path(path) => inputs::read(path)?,
+        };
+        let (solver, parse_time) = timing::time(|| day_gen.input(&text));
+        let solver = solver?;
+        let parts = [(1, part.p1()), (2, part.p2())]
+            .into_iter()
+            .filter(|(_, run)| *run)
+            .map(|(part, _)| {
+                let (answer, duration) = timing::time(|| {
+                    catch_panic(|| {
+                        if part == 1 {
+                            solver.part1()
+                        } else {
+                            solver.part2()
+                        }
+                    })
+                });
+                PartRun {
+                    part,
+                    answer,
+                    duration,
+                }
+            })
+            .collect();
+        Ok((parse_time, parts))
+    });
+    DayRun {
+        year,
+        day,
+        variant,
+        result,
+    }
+}
+
+/// Turns a panic in `f` into an error so it is reported for the day it happened in.
+fn catch_panic<T>(f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        Err(anyhow::anyhow!("panicked: {}", message))
+    })
+}
+
+/// Prints the answers of a day, returns whether there were no errors.
+fn print_run(run: &DayRun) -> bool {
+    match &run.result {
+        Ok((parse_time, parts)) => {
+            let variant = match run.variant {
+                aoc::DEFAULT_VARIANT => String::new(),
+                variant => format!(" ({})", variant),
+            };
+            println!(
+                "The solution for {} day {}{} is (parsed in {:.2?}):",
+                run.year, run.day, variant, parse_time
+            );
+            let mut ok = true;
+            for part in parts {
+                match &part.answer {
+                    Ok(answer) => {
+                        println!("part {} ({:.2?}): {}", part.part, part.duration, answer)
+                    }
+                    Err(e) => {
+                        eprintln!(
+                            "error in {} day {} part {}: {:#}",
+                            run.year, run.day, part.part, e
+                        );
+                        ok = false;
+                    }
+                }
+            }
+            ok
+        }
+        Err(e) => {
+            eprintln!("error in {} day {}: {:#}", run.year, run.day, e);
+            false
+        }
+    }
+}
+
+/// One part of a run in a machine readable format.
+struct Record {
+    year: i32,
+    day: u32,
+    variant: &'static str,
+    part: u8,
+    answer: Option<String>,
+    duration: Option<Duration>,
+    error: Option<String>,
+}
+
+/// Flattens a run into one record per part, a day that failed to load or
+/// parse gets a record with the error for each requested part.
+fn records(part: Part, run: &DayRun) -> Vec<Record> {
+    match &run.result {
+        Ok((_, parts)) => parts
+            .iter()
+            .map(|part| {
+                let (answer, error) = match &part.answer {
+                    Ok(Answer::Unimplemented) => (None, None),
+                    Ok(answer) => (Some(answer.to_machine()), None),
+                    Err(e) => (None, Some(format!("{:#}", e))),
+                };
+                Record {
+                    year: run.year,
+                    day: run.day,
+                    variant: run.variant,
+                    part: part.part,
+                    answer,
+                    duration: Some(part.duration),
+                    error,
+                }
+            })
+            .collect(),
+        Err(e) => [(1, part.p1()), (2, part.p2())]
+            .into_iter()
+            .filter(|(_, run)| *run)
+            .map(|(part, _)| Record {
+                year: run.year,
+                day: run.day,
+                variant: run.variant,
+                part,
+                answer: None,
+                duration: None,
+                error: Some(format!("{:#}", e)),
+            })
+            .collect(),
+    }
+}
+
+/// Prints every record, returns whether none of them is an error.
+fn print_records(records: &[Record], line: fn(&Record) -> String) -> bool {
+    for record in records {
+        println!("{}", line(record));
+    }
+    records.iter().all(|record| record.error.is_none())
+}
+
+/// The duration is in seconds, art answers keep their newlines.
+fn json_line(record: &Record) -> String {
+    serde_json::json!({
+        "year": record.year,
+        "day": record.day,
+        "variant": record.variant,
+        "part": record.part,
+        "answer": record.answer,
+        "duration": record.duration.map(|d| d.as_secs_f64()),
+        "error": record.error,
+    })
+    .to_string()
+}
+
+/// Escapes tabs, newlines and backslashes, missing values are empty.
+fn tsv_row(record: &Record) -> String {
+    let escape = |field: &Option<String>| {
+        field
+            .as_deref()
+            .unwrap_or_default()
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+    };
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        record.year,
+        record.day,
+        record.variant,
+        record.part,
+        escape(&record.answer),
+        record
+            .duration
+            .map(|d| d.as_secs_f64().to_string())
+            .unwrap_or_default(),
+        escape(&record.error),
+    )
+}
+
+/// Calls `f` on every item using up to `jobs` threads and hands the results
+/// to `emit` in the order of `items` as soon as they are available.
+fn for_each_ordered<I, T>(
+    jobs: usize,
+    items: &[I],
+    f: impl Fn(&I) -> T + Sync,
+    mut emit: impl FnMut(T),
+) where
+    I: Sync,
+    T: Send,
+{
+    if jobs <= 1 {
+        items.iter().map(f).for_each(emit);
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, atomic::Ordering::Relaxed);
+                match items.get(i) {
+                    Some(item) => {
+                        let _ = sender.send((i, f(item)));
+                    }
+                    None => break,
+                }
+            });
+        }
+        drop(sender);
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+        for (i, result) in receiver {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next_index) {
+                emit(result);
+                next_index += 1;
+            }
+        }
+    });
+}
+
+/// Runs every sample of a day and compares the results to the expected answers.
+fn run_samples(
+    day: &(dyn aoc::DayGen + Sync),
+    input_dir: &Path,
+    year: i32,
     day_num: u32,
-    sample: bool,
     part: Part,
-) {
-    let day = day.input(if sample { sample_str } else { input_str });
-    println!("The solution for day {} is:", day_num,);
-    if part.p1() {
-        println!("part 1: {}", day.part1(),);
+) -> anyhow::Result<()> {
+    let samples = if inputs::has_samples(input_dir, year, day_num) {
+        inputs::samples(input_dir, year, day_num)?
+    } else {
+        inputs::samples(&inputs::crate_dir(), year, day_num)?
+    };
+    println!("The samples for day {} are:", day_num);
+    let mut failures = 0;
+    for sample in &samples {
+        let solver = match day.input(&sample.text) {
+            Ok(solver) => solver,
+            Err(e) => {
+                println!("{}: FAILED to parse: {:#}", sample.name, e);
+                failures += 1;
+                continue;
+            }
+        };
+        for (part_num, run, expected) in
+            [(1, part.p1(), &sample.part1), (2, part.p2(), &sample.part2)]
+        {
+            if !run {
+                continue;
+            }
+            let answer = if part_num == 1 {
+                solver.part1()
+            } else {
+                solver.part2()
+            };
+            match (answer, expected) {
+                (Ok(answer), Some(expected)) if &answer == expected => {
+                    println!("{} part {}: {} ok", sample.name, part_num, answer)
+                }
+                (Ok(answer), Some(expected)) => {
+                    println!(
+                        "{} part {}: {} FAILED, expected {}",
+                        sample.name, part_num, answer, expected
+                    );
+                    failures += 1;
+                }
+                (Ok(answer), None) => println!(
+                    "{} part {}: {} (no expected answer)",
+                    sample.name, part_num, answer
+                ),
+                (Err(e), _) => {
+                    println!("{} part {}: FAILED: {:#}", sample.name, part_num, e);
+                    failures += 1;
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(anyhow::anyhow!("{} sample checks failed", failures));
     }
-    if part.p2() {
-        println!("part 2: {}", day.part2(),);
+    Ok(())
+}
+
+/// Runs the selected days and prints how their answers compare to the answer
+/// database, returns whether none of them was wrong or failed.
+fn verify(
+    input_dir: &Path,
+    years: Option<&List<i32>>,
+    days: Option<&List<u32>>,
+) -> anyhow::Result<bool> {
+    let db = AnswerDb::load(&input_dir.join(answers::FILE_NAME))?;
+    let days = select(years, days)?;
+
+    let mut ok = true;
+    println!("year day variant    part  status   answer               expected");
+    for (year, day, info) in days {
+        let text = inputs::load(input_dir, year, day, Kind::Input);
+        let no_input = text
+            .as_ref()
+            .err()
+            .and_then(|e| e.downcast_ref::<io::Error>())
+            .map(io::Error::kind)
+            == Some(io::ErrorKind::NotFound);
+        // the answers of the default variant, the others have to agree with
+        // them when there are no known answers
+        let mut main_answers: Option<[Option<Answer>; 2]> = None;
+        for (variant, day_gen) in info.variants() {
+            let answers = text
+                .as_ref()
+                .map_err(|e| anyhow::anyhow!("{:#}", e))
+                .and_then(|text| {
+                    let solver = catch_panic(|| day_gen.input(text))?;
+                    Ok([
+                        catch_panic(|| solver.part1()),
+                        catch_panic(|| solver.part2()),
+                    ])
+                });
+            for part in [1, 2] {
+                let answer = match &answers {
+                    Ok(answers) => answers[usize::from(part) - 1].as_ref(),
+                    Err(e) => Err(e),
+                };
+                let main_answer = main_answers
+                    .as_ref()
+                    .and_then(|answers| answers[usize::from(part) - 1].as_ref());
+                let expected = db.get(year, day, part);
+                let (status, answer) = match (answer, expected) {
+                    (Err(_), _) if no_input => ("NO INPUT", String::new()),
+                    (Err(e), _) => ("ERROR", format!("{:#}", e)),
+                    (Ok(answer), None) if main_answer.is_some() && main_answer != Some(answer) => {
+                        ("DIFFERS", cell(answer))
+                    }
+                    (Ok(answer), None) => ("MISSING", cell(answer)),
+                    (Ok(answer), Some(expected)) if answer == expected => ("OK", cell(answer)),
+                    (Ok(answer), Some(_)) => ("WRONG", cell(answer)),
+                };
+                // a missing input only fails when there is an answer to check
+                if matches!(status, "ERROR" | "WRONG" | "DIFFERS")
+                    || (status == "NO INPUT" && expected.is_some())
+                {
+                    ok = false;
+                }
+                println!(
+                    "{:<4} {:>3} {:<10} {:>4}  {:<8} {:<20} {}",
+                    year,
+                    day,
+                    variant,
+                    part,
+                    status,
+                    answer,
+                    expected.or(main_answer).map(cell).unwrap_or_default()
+                );
+            }
+            if main_answers.is_none() {
+                main_answers = Some(match &answers {
+                    Ok([part1, part2]) => {
+                        [part1.as_ref().ok().cloned(), part2.as_ref().ok().cloned()]
+                    }
+                    Err(_) => [None, None],
+                });
+            }
+        }
+    }
+    Ok(ok)
+}
+
+/// An answer on a single line.
+fn cell(answer: &Answer) -> String {
+    answer.to_machine().replace('\n', "\\n")
+}
+
+type DayGenRef = &'static (dyn aoc::DayGen + Sync);
+type DayInfoRef = &'static aoc::DayInfo;
+
+/// The registered days of `year` (or all years) filtered by `day`.
+fn select(
+    years: Option<&List<i32>>,
+    days: Option<&List<u32>>,
+) -> anyhow::Result<Vec<(i32, u32, DayInfoRef)>> {
+    if let Some(days) = days {
+        if !days.within(1..=25) {
+            return Err(anyhow::anyhow!("days go from 1 to 25"));
+        }
+    }
+    if let Some(year) =
+        years.and_then(|years| years.singles().find(|y| !aoc::YEARS.contains_key(y)))
+    {
+        let available: Vec<_> = aoc::YEARS.keys().map(|y| y.to_string()).collect();
+        return Err(anyhow::anyhow!(
+            "there are no solutions for {}, available years are {}",
+            year,
+            available.join(", ")
+        ));
+    }
+    let selected: Vec<_> = aoc::YEARS
+        .iter()
+        .filter(|(y, _)| years.is_none() || years.unwrap().contains(**y))
+        .flat_map(|(y, days)| days.iter().map(move |(d, info)| (*y, *d, info)))
+        .filter(|(_, d, _)| days.is_none() || days.unwrap().contains(*d))
+        .collect();
+    if let Some(day) = days.and_then(|days| {
+        days.singles()
+            .find(|d| selected.iter().all(|(_, s, _)| s != d))
+    }) {
+        return Err(anyhow::anyhow!(
+            "there is no solution for day {} in the selected years",
+            day
+        ));
+    }
+    if selected.is_empty() {
+        return Err(anyhow::anyhow!("no solutions match the selection"));
+    }
+    Ok(selected)
+}
+
+/// Picks the variant called `name` (or the default) of every day, days
+/// without it are left out.
+fn variants(
+    days: &[(i32, u32, DayInfoRef)],
+    name: Option<&str>,
+) -> anyhow::Result<Vec<(i32, u32, &'static str, DayGenRef)>> {
+    let name = match name {
+        Some(name) => name,
+        None => {
+            return Ok(days
+                .iter()
+                .map(|&(year, day, info)| {
+                    let (variant, day_gen) = info.main();
+                    (year, day, variant, day_gen)
+                })
+                .collect())
+        }
+    };
+    let selected: Vec<_> = days
+        .iter()
+        .filter_map(|&(year, day, info)| {
+            info.variants()
+                .find(|(variant, _)| *variant == name)
+                .map(|(variant, day_gen)| (year, day, variant, day_gen))
+        })
+        .collect();
+    match days {
+        [(year, day, info)] if selected.is_empty() => {
+            let variants: Vec<_> = info.variants().map(|(variant, _)| variant).collect();
+            Err(anyhow::anyhow!(
+                "{} day {} has no variant {:?}, its variants are {}",
+                year,
+                day,
+                name,
+                variants.join(", ")
+            ))
+        }
+        _ if selected.is_empty() => Err(anyhow::anyhow!(
+            "none of the selected days has a variant {:?}",
+            name
+        )),
+        _ => Ok(selected),
+    }
+}
+
+/// Downloads the inputs of the selected days that aren't in `input_dir` yet,
+/// returns whether all of them are there now.
+fn fetch_inputs(
+    input_dir: &Path,
+    years: Option<&List<i32>>,
+    days: Option<&List<u32>>,
+    base_url: &str,
+) -> anyhow::Result<bool> {
+    let now = clock::now()?;
+    let years: Vec<i32> = years.map_or_else(
+        || vec![clock::event_year(now)],
+        |years| years.iter().collect(),
+    );
+    if let Some(year) = years.iter().find(|&&year| year < aoc::MIN_YEAR) {
+        return Err(anyhow::anyhow!(
+            "there is no AoC in {}, it started in {}",
+            year,
+            aoc::MIN_YEAR
+        ));
+    }
+    if days.is_some() && !days.unwrap().within(1..=25) {
+        return Err(anyhow::anyhow!("days go from 1 to 25"));
+    }
+    let client = fetch::Client::new(base_url, &session::load()?.cookie)?;
+    let titles_path = input_dir.join(titles::FILE_NAME);
+    let mut titles = Titles::load(&titles_path)?;
+    let mut new_titles = false;
+    let mut ok = true;
+    for year in years {
+        for day in days.map_or_else(
+            || (1..=25).collect(),
+            |days| days.iter().collect::<Vec<_>>(),
+        ) {
+            if !clock::released(year, day, now) {
+                // only complain about days that were asked for explicitly
+                if days.is_some() {
+                    eprintln!("error: {} day {} is not released yet", year, day);
+                    ok = false;
+                }
+                continue;
+            }
+            match fetch::fetch(&client, input_dir, year, day) {
+                Ok((path, true)) => {
+                    println!("downloaded {} day {} to {}", year, day, path.display())
+                }
+                Ok((_, false)) => {}
+                Err(e) => {
+                    eprintln!("error in {} day {}: {:#}", year, day, e);
+                    ok = false;
+                    continue;
+                }
+            }
+            if titles.get(year, day).is_none() {
+                // a missing title is no reason to fail, it's only shown by `aoc status`
+                match client.title(year, day) {
+                    Ok(title) => {
+                        titles.insert(year, day, title);
+                        new_titles = true;
+                    }
+                    Err(e) => eprintln!("warning: no title for {} day {}: {:#}", year, day, e),
+                }
+            }
+        }
+    }
+    if new_titles {
+        titles.save(&titles_path)?;
+    }
+    Ok(ok)
+}
+
+/// Creates the solution of a day and tells where its input and samples go.
+fn new_day(input_dir: &Path, year: i32, day: u32, template: Option<&Path>) -> anyhow::Result<bool> {
+    if year < aoc::MIN_YEAR {
+        return Err(anyhow::anyhow!(
+            "there is no AoC in {}, it started in {}",
+            year,
+            aoc::MIN_YEAR
+        ));
+    }
+    if !(1..=25).contains(&day) {
+        return Err(anyhow::anyhow!("days go from 1 to 25"));
+    }
+    let template = match template {
+        Some(path) => inputs::read(path)? + "\n",
+        None => scaffold::DEFAULT_TEMPLATE.to_string(),
+    };
+    let path = scaffold::create(&inputs::crate_dir(), year, day, &template)?;
+    let sample_dir = inputs::path(input_dir, year, day, Kind::Sample).with_extension("");
+    println!(
+        "created {}, it is registered on the next build",
+        path.display()
+    );
+    println!(
+        "the input goes into {}, `aoc fetch -y{} -d{}` downloads it",
+        inputs::path(input_dir, year, day, Kind::Input).display(),
+        year,
+        day
+    );
+    println!(
+        "samples go into {}, their expected answers into {}",
+        sample_dir.join("{name}.txt").display(),
+        sample_dir.join(inputs::EXPECTED_FILE).display()
+    );
+    Ok(true)
+}
+
+/// Submits the answer of a part, solving it unless `answer` is given, returns
+/// whether it was right.
+fn submit_answer(
+    input_dir: &Path,
+    year: i32,
+    day: u32,
+    part: u8,
+    answer: Option<&str>,
+    base_url: &str,
+) -> anyhow::Result<bool> {
+    if !(1..=2).contains(&part) {
+        return Err(anyhow::anyhow!("parts are 1 and 2"));
+    }
+    let now = clock::now()?;
+    if !clock::released(year, day, now) {
+        return Err(anyhow::anyhow!("{} day {} is not released yet", year, day));
+    }
+    let answer = match answer {
+        Some(answer) => answer.parse().unwrap(),
+        None => {
+            let (_, _, info) = select(Some(&year.into()), Some(&day.into()))?[0];
+            let text = inputs::load(input_dir, year, day, Kind::Input)?;
+            let answer = catch_panic(|| {
+                let solver = info.main().1.input(&text)?;
+                if part == 1 {
+                    solver.part1()
+                } else {
+                    solver.part2()
+                }
+            })?;
+            println!("{} day {} part {}: {}", year, day, part, answer);
+            answer
+        }
+    };
+    let client = fetch::Client::new(base_url, &session::load()?.cookie)?;
+    let verdict = submit::submit(
+        &client,
+        &input_dir.join(submit::FILE_NAME),
+        year,
+        day,
+        part,
+        &answer,
+        now,
+    )?;
+    println!("{}", verdict);
+    if verdict == Verdict::Right {
+        println!(
+            "add it to {} so `aoc verify` checks it from now on",
+            input_dir.join(answers::FILE_NAME).display()
+        );
+    }
+    Ok(verdict == Verdict::Right)
+}
+
+/// Prints the listing of the Intcode program in `file`, or only its
+/// instructions if `source` is set.
+fn disassemble(file: &Path, source: bool) -> anyhow::Result<bool> {
+    let program: Intcode = inputs::read(file)?
+        .trim()
+        .parse()
+        .with_context(|| format!("{} is no Intcode program", file.display()))?;
+    if source {
+        print!("{}", intcode::source(&program));
+    } else {
+        print!("{}", intcode::listing(&program));
+    }
+    Ok(true)
+}
+
+/// Debugs the Intcode program in `file` reading commands from stdin.
+fn debug(file: &Path) -> anyhow::Result<bool> {
+    if file == Path::new("-") {
+        return Err(anyhow::anyhow!(
+            "the commands are read from stdin, the program can't be"
+        ));
+    }
+    let program: Intcode = inputs::read(file)?
+        .trim()
+        .parse()
+        .with_context(|| format!("{} is no Intcode program", file.display()))?;
+    Debugger::new(Machine::from(program)).repl(io::stdin().lock(), io::stdout())?;
+    Ok(true)
+}
+
+/// Prints the Intcode program assembled from `file`.
+fn assemble(file: &Path) -> anyhow::Result<bool> {
+    let program = intcode::assemble(&inputs::read(file)?)
+        .with_context(|| format!("could not assemble {}", file.display()))?;
+    let words: Vec<_> = program.iter().map(i64::to_string).collect();
+    println!("{}", words.join(","));
+    Ok(true)
+}
+
+/// Tells whether the session is logged in and where it is configured.
+fn check_session(base_url: &str) -> anyhow::Result<bool> {
+    let session = session::load()?;
+    let logged_in = fetch::Client::new(base_url, &session.cookie)?.logged_in()?;
+    if logged_in {
+        println!("the session from {} is logged in", session.source);
+    } else {
+        println!(
+            "the session from {} is not logged in, it may have expired",
+            session.source
+        );
+    }
+    Ok(logged_in)
+}
+
+/// Lists the selected days with what is known about them.
+fn status(
+    input_dir: &Path,
+    years: Option<&List<i32>>,
+    days: Option<&List<u32>>,
+) -> anyhow::Result<bool> {
+    let titles = Titles::load(&input_dir.join(titles::FILE_NAME))?;
+    let db = AnswerDb::load(&input_dir.join(answers::FILE_NAME))?;
+    let rows: Vec<_> = select(years, days)?
+        .into_iter()
+        .map(|(year, day, info)| {
+            let variants: Vec<_> = info.variants().map(|(name, _)| name).collect();
+            let input = inputs::load(input_dir, year, day, Kind::Input).is_ok();
+            let answers = [1, 2]
+                .into_iter()
+                .filter(|&part| db.get(year, day, part).is_some())
+                .count();
+            (
+                year,
+                day,
+                if input { "yes" } else { "no" },
+                answers,
+                variants.join(","),
+                titles.get(year, day).unwrap_or("?"),
+            )
+        })
+        .collect();
+    let width = rows
+        .iter()
+        .map(|row| row.4.len())
+        .chain(["variants".len()])
+        .max()
+        .unwrap_or_default();
+    println!(
+        "year day input answers {:<width$} title",
+        "variants",
+        width = width
+    );
+    for (year, day, input, answers, variants, title) in rows {
+        println!(
+            "{:<4} {:>3} {:<5} {:>5}/2 {:<width$} {}",
+            year,
+            day,
+            input,
+            answers,
+            variants,
+            title,
+            width = width
+        );
+    }
+    Ok(true)
+}
+
+/// Prints the progress of every year from the first AoC on, the latest one
+/// being `current_year` or a later year that already has solutions.
+fn progress(
+    input_dir: &Path,
+    years: Option<&List<i32>>,
+    current_year: i32,
+    format: Format,
+) -> anyhow::Result<bool> {
+    let db = AnswerDb::load(&input_dir.join(answers::FILE_NAME))?;
+    let last_year = aoc::YEARS
+        .keys()
+        .copied()
+        .chain([current_year])
+        .max()
+        .unwrap_or(current_year);
+    let progress: Vec<_> = (aoc::MIN_YEAR..=last_year)
+        .filter(|&year| years.is_none() || years.unwrap().contains(year))
+        .map(|year| {
+            let days = aoc::YEARS.get(&year);
+            YearProgress::new(
+                year,
+                |day| days.and_then(|days| days.get(&day)).is_some(),
+                &db,
+            )
+        })
+        .collect();
+    if progress.is_empty() {
+        return Err(anyhow::anyhow!(
+            "no years match the selection, AoC runs from {} to {}",
+            aoc::MIN_YEAR,
+            last_year
+        ));
+    }
+    match format {
+        Format::Text => {
+            for year in &progress {
+                println!("{}", year);
+            }
+            println!(
+                "{} answer known  {} implemented  {} missing",
+                Status::Known.symbol(),
+                Status::Implemented.symbol(),
+                Status::Missing.symbol()
+            );
+        }
+        Format::Json => {
+            for year in &progress {
+                println!("{}", year.to_json());
+            }
+        }
+        Format::Tsv => {
+            println!("year\tday\tpart1\tpart2");
+            for year in &progress {
+                for (day, [part1, part2]) in (1..).zip(&year.days) {
+                    println!("{}\t{}\t{}\t{}", year.year, day, part1.name(), part2.name());
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Tells which puzzle unlocks next and how long that takes.
+fn next_unlock(now: chrono::DateTime<chrono_tz::Tz>) -> anyhow::Result<bool> {
+    let (year, day, unlock) = clock::next_unlock(now);
+    let left = (unlock - now).num_seconds();
+    println!(
+        "{} day {} unlocks in {}d {:02}h {:02}m {:02}s, at {}",
+        year,
+        day,
+        left / 86400,
+        left % 86400 / 3600,
+        left % 3600 / 60,
+        left % 60,
+        unlock.format("%Y-%m-%d %H:%M:%S %Z")
+    );
+    Ok(true)
+}
+
+/// Runs parsing and both parts of the selected days `iterations` times and
+/// prints their timings, returns whether all of them succeeded.
+fn bench(
+    input_dir: &Path,
+    years: Option<&List<i32>>,
+    days: Option<&List<u32>>,
+    iterations: usize,
+) -> anyhow::Result<bool> {
+    if iterations == 0 {
+        return Err(anyhow::anyhow!("need at least one iteration"));
+    }
+    let mut ok = true;
+    // label, medians and whether it's the main variant of its day
+    let mut summary = Vec::new();
+    for (year, day, info) in select(years, days)? {
+        let (main, _) = info.main();
+        for (variant, day_gen) in info.variants() {
+            let label = match variant {
+                aoc::DEFAULT_VARIANT => format!("{} {:>3}", year, day),
+                variant => format!("{} {:>3} {}", year, day, variant),
+            };
+            match catch_panic(|| bench_day(input_dir, year, day, day_gen, iterations)) {
+                Ok(stats) => {
+                    println!("{}:", label);
+                    for (step, stats) in ["parse", "part 1", "part 2"].iter().zip(&stats) {
+                        println!(
+                            "  {:<6}  min {:>10}  median {:>10}  mean {:>10}",
+                            step,
+                            format!("{:.2?}", stats.min),
+                            format!("{:.2?}", stats.median),
+                            format!("{:.2?}", stats.mean),
+                        );
+                    }
+                    let medians = [
+                        stats[0].median,
+                        stats[1].median,
+                        stats[2].median,
+                        stats.iter().map(|s| s.median).sum(),
+                    ];
+                    summary.push((label, medians, variant == main));
+                }
+                Err(e) => {
+                    eprintln!("error in {}: {:#}", label, e);
+                    ok = false;
+                }
+            }
+        }
+    }
+
+    let width = summary
+        .iter()
+        .map(|(label, _, _)| label.len())
+        .chain(["year day".len()])
+        .max()
+        .unwrap_or_default();
+    println!();
+    println!("median per step:");
+    println!(
+        "{:<width$}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "year day",
+        "parse",
+        "part 1",
+        "part 2",
+        "total",
+        width = width
+    );
+    let mut totals = [Duration::ZERO; 4];
+    // the total of the last main variant to compare the other variants to
+    let mut main_total = Duration::ZERO;
+    for (label, medians, is_main) in &summary {
+        if *is_main {
+            for (total, median) in totals.iter_mut().zip(medians) {
+                *total += *median;
+            }
+            main_total = medians[3];
+            print_row(label, width, *medians, "");
+        } else {
+            // a main variant too fast to measure has nothing to compare to
+            let note = if main_total.is_zero() {
+                String::new()
+            } else {
+                let ratio = medians[3].as_secs_f64() / main_total.as_secs_f64();
+                format!("  {:.2}x the main variant", ratio)
+            };
+            print_row(label, width, *medians, &note);
+        }
+    }
+    print_row("total", width, totals, "");
+    Ok(ok)
+}
+
+fn print_row(label: &str, width: usize, durations: [Duration; 4], note: &str) {
+    let [parse, part1, part2, total] = durations.map(|d| format!("{:.2?}", d));
+    println!(
+        "{:<width$}  {:>10}  {:>10}  {:>10}  {:>10}{}",
+        label,
+        parse,
+        part1,
+        part2,
+        total,
+        note,
+        width = width
+    );
+}
+
+/// Timings of parsing, part 1 and part 2 of one day.
+fn bench_day(
+    input_dir: &Path,
+    year: i32,
+    day: u32,
+    day_gen: DayGenRef,
+    iterations: usize,
+) -> anyhow::Result<[Stats; 3]> {
+    let text = inputs::load(input_dir, year, day, Kind::Input)?;
+    let mut durations = [(); 3].map(|_| Vec::with_capacity(iterations));
+    for _ in 0..iterations {
+        let (solver, time) = timing::time(|| day_gen.input(&text));
+        let solver = solver?;
+        durations[0].push(time);
+        let (answer, time) = timing::time(|| solver.part1());
+        answer?;
+        durations[1].push(time);
+        let (answer, time) = timing::time(|| solver.part2());
+        answer?;
+        durations[2].push(time);
     }
+    Ok(durations.map(|d| Stats::new(&d).unwrap_or_default()))
 }
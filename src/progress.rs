@@ -0,0 +1,127 @@
+use crate::answers::AnswerDb;
+use serde_json::json;
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// there is no solution for the day
+    Missing,
+    /// there is a solution, but no known answer to check it against
+    Implemented,
+    /// there is a solution and the answer of the part is known, only
+    /// `aoc verify` checks that the solution still gives it
+    Known,
+}
+
+impl Status {
+    pub fn symbol(self) -> char {
+        match self {
+            Status::Missing => '.',
+            Status::Implemented => '+',
+            Status::Known => '*',
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Missing => "missing",
+            Status::Implemented => "implemented",
+            Status::Known => "known",
+        }
+    }
+}
+
+/// The status of both parts of every day of a year.
+#[derive(Clone, Debug)]
+pub struct YearProgress {
+    pub year: i32,
+    pub days: [[Status; 2]; 25],
+}
+
+impl YearProgress {
+    pub fn new(year: i32, implemented: impl Fn(u32) -> bool, db: &AnswerDb) -> Self {
+        let mut days = [[Status::Missing; 2]; 25];
+        for (day, parts) in (1..).zip(&mut days) {
+            if !implemented(day) {
+                continue;
+            }
+            for (part, status) in (1..).zip(parts) {
+                *status = match db.get(year, day, part) {
+                    Some(_) => Status::Known,
+                    None => Status::Implemented,
+                };
+            }
+        }
+        Self { year, days }
+    }
+
+    /// The parts with the given status.
+    pub fn count(&self, status: Status) -> usize {
+        self.days.iter().flatten().filter(|&&s| s == status).count()
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let days: Vec<_> = (1..)
+            .zip(&self.days)
+            .map(|(day, [part1, part2])| {
+                json!({"day": day, "part1": part1.name(), "part2": part2.name()})
+            })
+            .collect();
+        json!({
+            "year": self.year,
+            "known": self.count(Status::Known),
+            "implemented": self.count(Status::Implemented),
+            "days": days,
+        })
+    }
+}
+
+/// A calendar of five weeks with the symbols of both parts next to each day.
+impl fmt::Display for YearProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}  {}/50 answers known, {} implemented",
+            self.year,
+            self.count(Status::Known),
+            self.count(Status::Implemented)
+        )?;
+        for week in (1..).zip(&self.days).collect::<Vec<_>>().chunks(5) {
+            let cells: Vec<_> = week
+                .iter()
+                .map(|(day, [part1, part2])| {
+                    format!("{:>2} {}{}", day, part1.symbol(), part2.symbol())
+                })
+                .collect();
+            writeln!(f, "{}", cells.join("  "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_test() {
+        let db: AnswerDb = "[2021.1]\npart1 = 7\n\n[2021.3]\npart1 = 1\npart2 = 2\n"
+            .parse()
+            .unwrap();
+        let progress = YearProgress::new(2021, |day| day <= 2, &db);
+        assert_eq!(progress.days[0], [Status::Known, Status::Implemented]);
+        assert_eq!(progress.days[2], [Status::Missing; 2]);
+        assert_eq!(progress.count(Status::Known), 1);
+        assert_eq!(progress.count(Status::Implemented), 3);
+
+        let grid = progress.to_string();
+        let lines: Vec<_> = grid.lines().collect();
+        assert_eq!(lines[0], "2021  1/50 answers known, 3 implemented");
+        assert_eq!(lines[1], " 1 *+   2 ++   3 ..   4 ..   5 ..");
+        assert_eq!(lines.len(), 6);
+
+        let json = progress.to_json();
+        assert_eq!(json["days"][1]["part2"], "implemented");
+        assert_eq!(json["known"], 1);
+    }
+}
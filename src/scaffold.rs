@@ -0,0 +1,67 @@
+use anyhow::{anyhow, Context, Result};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// The template used when none is given, `{{year}}`, `{{day}}` and
+/// `{{day:02}}` are replaced with the day being created.
+pub const DEFAULT_TEMPLATE: &str = include_str!("../templates/day.rs");
+
+/// `{dir}/year{year}/day{day}.rs`
+pub fn source_path(dir: &Path, year: i32, day: u32) -> PathBuf {
+    dir.join(format!("year{}", year))
+        .join(format!("day{:02}.rs", day))
+}
+
+pub fn render(template: &str, year: i32, day: u32) -> String {
+    template
+        .replace("{{year}}", &year.to_string())
+        .replace("{{day:02}}", &format!("{:02}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Writes the solution of the given day from `template`, an existing
+/// solution is never overwritten.
+pub fn create(dir: &Path, year: i32, day: u32, template: &str) -> Result<PathBuf> {
+    let path = source_path(dir, year, day);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("could not create {}", parent.display()))?;
+    }
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            return Err(anyhow!(
+                "{} already exists, not overwriting it",
+                path.display()
+            ))
+        }
+        Err(e) => return Err(e).with_context(|| format!("could not create {}", path.display())),
+    };
+    file.write_all(render(template, year, day).as_bytes())
+        .with_context(|| format!("could not write {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_test() {
+        assert_eq!(
+            render("{{year}} day {{day}} day{{day:02}}.rs", 2022, 3),
+            "2022 day 3 day03.rs"
+        );
+        assert!(!render(DEFAULT_TEMPLATE, 2022, 3).contains("{{"));
+
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+        let path = create(&dir, 2022, 3, "first").unwrap();
+        assert_eq!(path, dir.join("year2022").join("day03.rs"));
+        assert!(create(&dir, 2022, 3, "second").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
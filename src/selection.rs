@@ -0,0 +1,115 @@
+use anyhow::{anyhow, Context, Result};
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+/// A comma separated list of values and inclusive ranges as given on the
+/// command line, e.g. `2019,2021`, `1-10` or `5,7,12`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct List<T> {
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: Copy + Ord> List<T> {
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges.iter().any(|range| range.contains(&value))
+    }
+
+    /// The values that were given on their own instead of as part of a range.
+    pub fn singles(&self) -> impl Iterator<Item = T> + '_ {
+        self.ranges
+            .iter()
+            .filter(|range| range.start() == range.end())
+            .map(|range| *range.start())
+    }
+
+    /// Whether every value of the list lies in `bounds`.
+    pub fn within(&self, bounds: RangeInclusive<T>) -> bool {
+        self.ranges
+            .iter()
+            .all(|range| bounds.contains(range.start()) && bounds.contains(range.end()))
+    }
+
+    /// Every value of the list in the order they were given.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_
+    where
+        RangeInclusive<T>: Iterator<Item = T>,
+    {
+        self.ranges.iter().cloned().flatten()
+    }
+
+    /// Applies `f` to both ends of every range.
+    pub fn map(self, f: impl Fn(T) -> T) -> Self {
+        let ranges = self
+            .ranges
+            .into_iter()
+            .map(|range| f(*range.start())..=f(*range.end()))
+            .collect();
+        Self { ranges }
+    }
+}
+
+impl<T: Copy> From<T> for List<T> {
+    fn from(value: T) -> Self {
+        Self {
+            ranges: vec![value..=value],
+        }
+    }
+}
+
+impl<T> FromStr for List<T>
+where
+    T: FromStr + Copy + Ord + Display,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<T>()
+                .with_context(|| format!("invalid number {:?}", value))
+        };
+        let ranges = s
+            .split(',')
+            .map(|item| {
+                let (start, end) = match item.split_once('-') {
+                    Some((start, end)) => (parse(start)?, parse(end)?),
+                    None => {
+                        let value = parse(item)?;
+                        (value, value)
+                    }
+                };
+                if start > end {
+                    return Err(anyhow!("the range {}-{} is empty", start, end));
+                }
+                Ok(start..=end)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { ranges })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_test() {
+        let days: List<u32> = "5,7-9,12".parse().unwrap();
+        assert!([5, 7, 8, 9, 12].iter().all(|&day| days.contains(day)));
+        assert!(![1, 6, 10, 13].iter().any(|&day| days.contains(day)));
+        assert_eq!(days.singles().collect::<Vec<_>>(), [5, 12]);
+        assert_eq!(days.iter().collect::<Vec<_>>(), [5, 7, 8, 9, 12]);
+        assert!(days.within(1..=25));
+        assert!(!days.within(6..=25));
+        assert_eq!(
+            "19-21".parse::<List<i32>>().unwrap().map(|y| y + 2000),
+            List {
+                ranges: vec![2019..=2021]
+            }
+        );
+        assert!("10-1".parse::<List<u32>>().is_err());
+        assert!("1,x".parse::<List<u32>>().is_err());
+        assert!("".parse::<List<u32>>().is_err());
+    }
+}
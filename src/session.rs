@@ -0,0 +1,121 @@
+use anyhow::{anyhow, Context, Result};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The AoC session cookie and where it was found.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Session {
+    /// the cookie header, i.e. `session=53616c74...`
+    pub cookie: String,
+    pub source: String,
+}
+
+/// The file holding the session cookie, e.g. `session=53616c74...`.
+pub fn cookie_file() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("cookie")
+}
+
+/// The user config, `$XDG_CONFIG_HOME/aoc/config.toml` or
+/// `~/.config/aoc/config.toml`, which can contain `session = "53616c74..."`.
+pub fn config_file() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("aoc").join("config.toml"))
+}
+
+/// Finds the session in `AOC_SESSION`, the [`config_file`] or the
+/// [`cookie_file`], in that order.
+pub fn load() -> Result<Session> {
+    from_sources(
+        env::var("AOC_SESSION").ok(),
+        config_file().as_deref(),
+        &cookie_file(),
+    )
+}
+
+fn from_sources(var: Option<String>, config: Option<&Path>, cookie: &Path) -> Result<Session> {
+    if let Some(var) = var.filter(|var| !var.trim().is_empty()) {
+        return Ok(Session {
+            cookie: cookie_header(&var),
+            source: "AOC_SESSION".to_string(),
+        });
+    }
+    if let Some(config) = config {
+        if let Some(text) = read_optional(config)? {
+            let value: toml::Value = text
+                .parse()
+                .with_context(|| format!("invalid config in {}", config.display()))?;
+            if let Some(session) = value.get("session") {
+                let session = session.as_str().ok_or_else(|| {
+                    anyhow!("the session in {} is not a string", config.display())
+                })?;
+                return Ok(Session {
+                    cookie: cookie_header(session),
+                    source: config.display().to_string(),
+                });
+            }
+        }
+    }
+    match read_optional(cookie)? {
+        Some(text) if !text.trim().is_empty() => Ok(Session {
+            cookie: cookie_header(&text),
+            source: cookie.display().to_string(),
+        }),
+        _ => Err(anyhow!(
+            "no session found, set AOC_SESSION, put `session = \"...\"` into {} or `session=...` into {}",
+            config.map_or_else(|| "the config".to_string(), |c| c.display().to_string()),
+            cookie.display()
+        )),
+    }
+}
+
+/// Accepts the bare token as well as `session=token`.
+fn cookie_header(session: &str) -> String {
+    let session = session.trim();
+    if session.starts_with("session=") {
+        session.to_string()
+    } else {
+        format!("session={}", session)
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("could not read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sources_test() {
+        let dir = env::temp_dir().join(format!("aoc-session-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("config.toml");
+        let cookie = dir.join("cookie");
+        let cookie_of = |var: Option<&str>| {
+            from_sources(var.map(String::from), Some(&config), &cookie).map(|s| s.cookie)
+        };
+
+        assert!(cookie_of(None).is_err());
+        fs::write(&cookie, "session=abc\n").unwrap();
+        assert_eq!(cookie_of(None).unwrap(), "session=abc");
+        fs::write(&config, "session = \"def\"\n").unwrap();
+        assert_eq!(cookie_of(None).unwrap(), "session=def");
+        assert_eq!(cookie_of(Some("ghi")).unwrap(), "session=ghi");
+        assert_eq!(cookie_of(Some("")).unwrap(), "session=def");
+        fs::write(&config, "other = 1\n").unwrap();
+        assert_eq!(cookie_of(None).unwrap(), "session=abc");
+        fs::write(&config, "session = 1\n").unwrap();
+        assert!(cookie_of(None).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
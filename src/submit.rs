@@ -0,0 +1,408 @@
+use crate::{fetch::Client, Answer};
+use anyhow::{anyhow, Context, Result};
+use chrono::DateTime;
+use chrono_tz::Tz;
+use std::{fmt, fs, io, path::Path, time::Duration};
+
+pub const FILE_NAME: &str = "guesses.toml";
+
+/// What AoC thinks of a submitted answer.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// the last answer was too recent, the answer wasn't checked
+    Wait(Option<Duration>),
+    /// the part is already solved, the answer wasn't checked
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Reads the verdict from the page answering a submission.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Verdict::Right)
+        } else if page.contains("That's not the right answer") {
+            Ok(if page.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if page.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else if page.contains("You gave an answer too recently") {
+            Ok(Verdict::Wait(parse_wait(page)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::AlreadySolved)
+        } else {
+            Err(anyhow!("could not make sense of the answer page"))
+        }
+    }
+
+    /// The name it is saved under.
+    pub fn name(self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wait(_) => "wait",
+            Verdict::AlreadySolved => "already solved",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "right" => Verdict::Right,
+            "wrong" => Verdict::Wrong,
+            "too high" => Verdict::TooHigh,
+            "too low" => Verdict::TooLow,
+            "wait" => Verdict::Wait(None),
+            "already solved" => Verdict::AlreadySolved,
+            _ => return None,
+        })
+    }
+
+    /// Whether the answer was judged to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => f.write_str("that's the right answer"),
+            Verdict::Wrong => f.write_str("that's not the right answer"),
+            Verdict::TooHigh => f.write_str("that's not the right answer, it is too high"),
+            Verdict::TooLow => f.write_str("that's not the right answer, it is too low"),
+            Verdict::Wait(Some(left)) => write!(
+                f,
+                "the last answer was too recent, try again in {}s",
+                left.as_secs()
+            ),
+            Verdict::Wait(None) => f.write_str("the last answer was too recent, try again later"),
+            Verdict::AlreadySolved => f.write_str("the part is already solved"),
+        }
+    }
+}
+
+/// The time left in `You have 1m 23s left to wait.`
+fn parse_wait(page: &str) -> Option<Duration> {
+    let left = page.split("You have ").nth(1)?.split(" left").next()?;
+    left.split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+            let number: u64 = number.parse().ok()?;
+            match unit {
+                "h" => Some(number * 3600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Guess {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub answer: Answer,
+    pub verdict: Verdict,
+    /// when it was submitted, RFC 3339
+    pub time: String,
+}
+
+/// Every answer submitted so far, stored as
+///
+/// ```toml
+/// [[guess]]
+/// year = 2021
+/// day = 1
+/// part = 1
+/// answer = "1233"
+/// verdict = "too low"
+/// time = "2021-12-01T00:04:12-05:00"
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Guesses {
+    guesses: Vec<Guess>,
+}
+
+impl Guesses {
+    /// Loads the guesses at `path`, a missing file has no guesses.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("could not read {}", path.display())),
+        };
+        Self::parse(&text).with_context(|| format!("invalid guesses in {}", path.display()))
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let value: toml::Value = text.parse()?;
+        let guesses = match value.get("guess") {
+            Some(guesses) => guesses
+                .as_array()
+                .ok_or_else(|| anyhow!("expected an array of guesses"))?,
+            None => return Ok(Self::default()),
+        };
+        let guesses = guesses
+            .iter()
+            .map(|guess| {
+                let int = |key: &str| {
+                    guess
+                        .get(key)
+                        .and_then(toml::Value::as_integer)
+                        .ok_or_else(|| anyhow!("a guess is missing the number {:?}", key))
+                };
+                let string = |key: &str| {
+                    guess
+                        .get(key)
+                        .and_then(toml::Value::as_str)
+                        .ok_or_else(|| anyhow!("a guess is missing the string {:?}", key))
+                };
+                let verdict = string("verdict")?;
+                Ok(Guess {
+                    year: int("year")?.try_into()?,
+                    day: int("day")?.try_into()?,
+                    part: int("part")?.try_into()?,
+                    answer: string("answer")?.parse().unwrap(),
+                    verdict: Verdict::from_name(verdict)
+                        .ok_or_else(|| anyhow!("unknown verdict {:?}", verdict))?,
+                    time: string("time")?.to_string(),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { guesses })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let guesses = self
+            .guesses
+            .iter()
+            .map(|guess| {
+                let mut table = toml::value::Table::new();
+                table.insert("year".into(), guess.year.into());
+                table.insert("day".into(), guess.day.into());
+                table.insert("part".into(), guess.part.into());
+                table.insert("answer".into(), guess.answer.to_machine().into());
+                table.insert("verdict".into(), guess.verdict.name().into());
+                table.insert("time".into(), guess.time.clone().into());
+                toml::Value::Table(table)
+            })
+            .collect::<Vec<_>>();
+        let mut document = toml::value::Table::new();
+        document.insert("guess".into(), guesses.into());
+        fs::write(path, toml::to_string(&document)?)
+            .with_context(|| format!("could not write {}", path.display()))
+    }
+
+    /// The guesses for a part, oldest first.
+    pub fn of(&self, year: i32, day: u32, part: u8) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |g| (g.year, g.day, g.part) == (year, day, part))
+    }
+
+    pub fn record(&mut self, guess: Guess) {
+        self.guesses.push(guess);
+    }
+
+    /// Fails if submitting `answer` is pointless: the part is solved already,
+    /// the same answer was wrong before or it is out of the bounds earlier
+    /// guesses that were too high or too low set.
+    pub fn check(&self, year: i32, day: u32, part: u8, answer: &Answer) -> Result<()> {
+        match answer {
+            Answer::Unimplemented => return Err(anyhow!("the part is not implemented")),
+            Answer::Art(_) => {
+                return Err(anyhow!(
+                    "art has to be read by a human, submit the letters with --answer"
+                ))
+            }
+            _ => {}
+        }
+        for guess in self.of(year, day, part) {
+            match guess.verdict {
+                Verdict::Right => {
+                    return Err(anyhow!(
+                        "the part is already solved, the answer was {}",
+                        guess.answer
+                    ))
+                }
+                verdict if verdict.is_wrong() && &guess.answer == answer => {
+                    return Err(anyhow!(
+                        "{} was already submitted on {}: {}",
+                        answer,
+                        guess.time,
+                        verdict
+                    ))
+                }
+                _ => {}
+            }
+        }
+        if let Answer::Integer(n) = answer {
+            let bound = |verdict| {
+                self.of(year, day, part)
+                    .filter(move |guess| guess.verdict == verdict)
+                    .filter_map(|guess| match guess.answer {
+                        Answer::Integer(n) => Some(n),
+                        _ => None,
+                    })
+            };
+            if let Some(high) = bound(Verdict::TooHigh).min().filter(|high| n >= high) {
+                return Err(anyhow!("{} can't be right, {} was too high", n, high));
+            }
+            if let Some(low) = bound(Verdict::TooLow).max().filter(|low| n <= low) {
+                return Err(anyhow!("{} can't be right, {} was too low", n, low));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Submits `answer` unless the guesses at `path` show that is pointless and
+/// adds it to them.
+pub fn submit(
+    client: &Client,
+    path: &Path,
+    year: i32,
+    day: u32,
+    part: u8,
+    answer: &Answer,
+    now: DateTime<Tz>,
+) -> Result<Verdict> {
+    let mut guesses = Guesses::load(path)?;
+    guesses
+        .check(year, day, part, answer)
+        .context("not submitting the answer")?;
+    let page = client.answer(year, day, part, &answer.to_machine())?;
+    let verdict = Verdict::parse(&page)?;
+    guesses.record(Guess {
+        year,
+        day,
+        part,
+        answer: answer.clone(),
+        verdict,
+        time: now.to_rfc3339(),
+    });
+    guesses.save(path)?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::tests::{client, serve, temp_dir};
+
+    #[test]
+    fn verdict_test() {
+        let page = |text: &str| format!("<main><article><p>{}</p></article></main>", text);
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's the right answer!  You are one gold star closer."
+            ))
+            .unwrap(),
+            Verdict::Right
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "That's not the right answer; your answer is too high.  Please wait one minute."
+            ))
+            .unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse(&page("That's not the right answer.  If you're stuck, ...")).unwrap(),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 23s left to wait."
+            ))
+            .unwrap(),
+            Verdict::Wait(Some(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            Verdict::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap(),
+            Verdict::AlreadySolved
+        );
+        assert!(Verdict::parse(&page("Puzzle inputs differ by user.")).is_err());
+    }
+
+    #[test]
+    fn check_test() {
+        let mut guesses = Guesses::default();
+        let guess = |answer: &str, verdict| Guess {
+            year: 2021,
+            day: 1,
+            part: 1,
+            answer: answer.parse().unwrap(),
+            verdict,
+            time: "2021-12-01T00:04:12-05:00".to_string(),
+        };
+        guesses.record(guess("100", Verdict::TooHigh));
+        guesses.record(guess("10", Verdict::TooLow));
+        guesses.record(guess("50", Verdict::Wrong));
+        guesses.record(guess("abc", Verdict::Wrong));
+        let check =
+            |guesses: &Guesses, answer: &str| guesses.check(2021, 1, 1, &answer.parse().unwrap());
+        assert!(check(&guesses, "100").is_err());
+        assert!(check(&guesses, "150").is_err());
+        assert!(check(&guesses, "10").is_err());
+        assert!(check(&guesses, "50").is_err());
+        assert!(check(&guesses, "abc").is_err());
+        assert!(check(&guesses, "").is_err());
+        assert!(check(&guesses, "42").is_ok());
+        assert!(check(&guesses, "abd").is_ok());
+        assert!(guesses.check(2021, 1, 2, &Answer::Integer(150)).is_ok());
+
+        guesses.record(guess("42", Verdict::Right));
+        assert!(check(&guesses, "43").is_err());
+
+        let parsed = Guesses::parse(&{
+            let path = temp_dir("guesses-test.toml");
+            guesses.save(&path).unwrap();
+            let text = fs::read_to_string(&path).unwrap();
+            fs::remove_file(&path).unwrap();
+            text
+        })
+        .unwrap();
+        assert_eq!(parsed.guesses, guesses.guesses);
+    }
+
+    #[test]
+    fn submit_test() {
+        let (base_url, server) = serve(vec![(
+            200,
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        )]);
+        let client = client(&base_url);
+        let path = temp_dir("submit-test.toml");
+        let now = "2021-12-01T00:05:00-05:00"
+            .parse::<DateTime<chrono::FixedOffset>>()
+            .unwrap()
+            .with_timezone(&chrono_tz::US::Eastern);
+        let submit = |answer| submit(&client, &path, 2021, 1, 2, &Answer::Integer(answer), now);
+        assert_eq!(submit(7).unwrap(), Verdict::TooLow);
+        // refused without asking the server, it only answers once
+        assert!(submit(5).is_err());
+        let guesses = Guesses::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(guesses.of(2021, 1, 2).count(), 1);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests[0][0], "POST /2021/day/1/answer HTTP/1.1");
+        assert_eq!(requests[0].last().unwrap(), "level=2&answer=7");
+    }
+}
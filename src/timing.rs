@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+/// Runs `f` and measures how long it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Summary of repeated measurements.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// `None` if there are no measurements.
+    pub fn new(durations: &[Duration]) -> Option<Self> {
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len();
+        let min = *sorted.first()?;
+        let median = (sorted[(n - 1) / 2] + sorted[n / 2]) / 2;
+        let mean = sorted.iter().sum::<Duration>() / n as u32;
+        Some(Self { min, median, mean })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_test() {
+        let ms = Duration::from_millis;
+        assert_eq!(Stats::new(&[]), None);
+        assert_eq!(
+            Stats::new(&[ms(4), ms(1), ms(10)]),
+            Some(Stats {
+                min: ms(1),
+                median: ms(4),
+                mean: ms(5),
+            })
+        );
+        assert_eq!(
+            Stats::new(&[ms(2), ms(1), ms(3), ms(6)]).unwrap().median,
+            ms(2) + ms(1) / 2
+        );
+    }
+}
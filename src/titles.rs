@@ -0,0 +1,118 @@
+use anyhow::{anyhow, Context, Result};
+use std::{collections::BTreeMap, fs, io, path::Path, str::FromStr};
+
+pub const FILE_NAME: &str = "titles.toml";
+
+/// The titles of the puzzles, stored as
+///
+/// ```toml
+/// [2021]
+/// 1 = "Sonar Sweep"
+/// 2 = "Dive!"
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Titles {
+    titles: BTreeMap<(i32, u32), String>,
+}
+
+impl Titles {
+    /// Loads the titles at `path`, a missing file has no titles.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .with_context(|| format!("invalid titles in {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("could not read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .with_context(|| format!("could not write {}", path.display()))
+    }
+
+    pub fn get(&self, year: i32, day: u32) -> Option<&str> {
+        self.titles.get(&(year, day)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, year: i32, day: u32, title: String) {
+        self.titles.insert((year, day), title);
+    }
+}
+
+impl FromStr for Titles {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value: toml::Value = s.parse()?;
+        let years = value
+            .as_table()
+            .ok_or_else(|| anyhow!("expected a table of years"))?;
+        let mut titles = BTreeMap::new();
+        for (year, days) in years {
+            let year: i32 = year
+                .parse()
+                .with_context(|| format!("invalid year {:?}", year))?;
+            let days = days
+                .as_table()
+                .ok_or_else(|| anyhow!("expected a table of days for {}", year))?;
+            for (day, title) in days {
+                let day: u32 = day
+                    .parse()
+                    .with_context(|| format!("invalid day {:?}", day))?;
+                let title = title
+                    .as_str()
+                    .ok_or_else(|| anyhow!("the title of {} day {} is not a string", year, day))?;
+                titles.insert((year, day), title.to_string());
+            }
+        }
+        Ok(Self { titles })
+    }
+}
+
+impl std::fmt::Display for Titles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut last_year = None;
+        for (&(year, day), title) in &self.titles {
+            if last_year != Some(year) {
+                if last_year.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[{}]", year)?;
+                last_year = Some(year);
+            }
+            writeln!(f, "{} = {}", day, toml::Value::String(title.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let mut titles: Titles = "[2021]\n2 = \"Dive!\"\n1 = \"Sonar Sweep\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!(titles.get(2021, 2), Some("Dive!"));
+        assert_eq!(titles.get(2021, 3), None);
+        titles.insert(2019, 1, "The Tyranny of the Rocket Equation".to_string());
+        assert_eq!(
+            titles.to_string(),
+            concat!(
+                "[2019]\n",
+                "1 = \"The Tyranny of the Rocket Equation\"\n",
+                "\n",
+                "[2021]\n",
+                "1 = \"Sonar Sweep\"\n",
+                "2 = \"Dive!\"\n",
+            )
+        );
+        titles.insert(2020, 8, "I Heard You Like \"Registers\"".to_string());
+        let parsed: Titles = titles.to_string().parse().unwrap();
+        assert_eq!(parsed.titles, titles.titles);
+    }
+}
@@ -0,0 +1,3 @@
+// the modules of the solutions and `YEARS` are generated by `build.rs` from
+// the `year{year}/day{day}.rs` files next to this one
+include!(concat!(env!("OUT_DIR"), "/years.rs"));
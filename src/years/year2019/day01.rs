@@ -1,12 +1,15 @@
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let input = input
-            .split('\n')
-            .filter_map(|line| line.parse().ok())
-            .collect();
-        Box::new(Day::new(input))
+            .lines()
+            .map(|line| {
+                line.parse()
+                    .map_err(|e| anyhow::anyhow!("invalid mass {:?}: {}", line, e))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -23,32 +26,28 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        format!(
-            "{}",
-            self.input.iter().map(|mass| mass / 3 - 2).sum::<i64>()
-        )
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.input.iter().map(|mass| mass / 3 - 2).sum::<i64>().into())
     }
 
-    fn part2(&self) -> String {
-        format!(
-            "{}",
-            self.input
-                .iter()
-                .map(|mass| {
-                    let mut mass = *mass;
-                    let mut module_fuel = 0;
-                    loop {
-                        let fuel = mass / 3 - 2;
-                        if fuel > 0 {
-                            module_fuel += fuel;
-                            mass = fuel;
-                        } else {
-                            break module_fuel;
-                        }
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self
+            .input
+            .iter()
+            .map(|mass| {
+                let mut mass = *mass;
+                let mut module_fuel = 0;
+                loop {
+                    let fuel = mass / 3 - 2;
+                    if fuel > 0 {
+                        module_fuel += fuel;
+                        mass = fuel;
+                    } else {
+                        break module_fuel;
                     }
-                })
-                .sum::<i64>()
-        )
+                }
+            })
+            .sum::<i64>()
+            .into())
     }
 }
@@ -2,8 +2,8 @@ use super::intcode::{Machine, Intcode};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::new(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::new(input)?))
     }
 }
 
@@ -12,32 +12,34 @@ struct Day {
 }
 
 impl Day {
-    pub fn new(input: &str) -> Self {
-        Self { input: input.parse().unwrap() }
+    pub fn new(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: input.parse()?,
+        })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut machine = Machine::from(&self.input[..]);
         machine.memory[1] = 12;
         machine.memory[2] = 2;
-        machine.run();
-        format!("{}", machine.memory[0])
+        machine.run()?;
+        Ok(machine.memory[0].into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         for noun in 0..=99 {
             for verb in 0..=99 {
                 let mut machine = Machine::from(&self.input[..]);
                 machine.memory[1] = noun;
                 machine.memory[2] = verb;
-                machine.run();
+                machine.run()?;
                 if machine.memory[0] == 19690720 {
-                    return format!("{}", 100 * noun + verb);
+                    return Ok((100 * noun + verb).into());
                 }
             }
         }
-        unreachable!();
+        Err(anyhow::anyhow!("no noun and verb produce 19690720"))
     }
 }
@@ -1,20 +1,22 @@
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let (wire_a, wire_b) = input.split_once('\n').unwrap();
-        Box::new(Day::new((
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        let (wire_a, wire_b) = input
+            .split_once('\n')
+            .ok_or_else(|| anyhow::anyhow!("expected two wires"))?;
+        Ok(Box::new(Day::new((
             wire_a
                 .split(',')
-                .filter_map(|dir| dir.trim().parse().ok())
-                .collect::<Vec<Direction>>()
+                .map(|dir| dir.trim().parse())
+                .collect::<anyhow::Result<Vec<Direction>>>()?
                 .into(),
             wire_b
                 .split(',')
-                .filter_map(|dir| dir.trim().parse().ok())
-                .collect::<Vec<Direction>>()
+                .map(|dir| dir.trim().parse())
+                .collect::<anyhow::Result<Vec<Direction>>>()?
                 .into(),
-        )))
+        ))))
     }
 }
 
@@ -31,12 +33,12 @@ enum Direction {
 impl std::str::FromStr for Direction {
     type Err = anyhow::Error;
     fn from_str(d: &str) -> anyhow::Result<Self> {
-        Ok(match &d[0..1] {
+        Ok(match d.get(0..1).unwrap_or_default() {
             "U" => Self::Up(d[1..].parse()?),
             "D" => Self::Down(d[1..].parse()?),
             "L" => Self::Left(d[1..].parse()?),
             "R" => Self::Right(d[1..].parse()?),
-            _ => return Err(anyhow::anyhow!("invalid input")),
+            _ => return Err(anyhow::anyhow!("invalid direction {:?}", d)),
         })
     }
 }
@@ -189,23 +191,23 @@ impl From<Vec<Point>> for Wire {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        Wire::crossings(&self.input.0, &self.input.1)
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(Wire::crossings(&self.input.0, &self.input.1)
             .iter()
             .filter(|p| p != &&Point::new())
             .map(Point::manhattan)
             .min()
-            .unwrap()
-            .to_string()
+            .ok_or_else(|| anyhow::anyhow!("the wires never cross"))?
+            .into())
     }
 
-    fn part2(&self) -> String {
-        Wire::crossings(&self.input.0, &self.input.1)
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(Wire::crossings(&self.input.0, &self.input.1)
             .iter()
             .filter(|p| p != &&Point::new())
             .map(|p| self.input.0.distance(*p) + self.input.1.distance(*p))
             .min()
-            .unwrap()
-            .to_string()
+            .ok_or_else(|| anyhow::anyhow!("the wires never cross"))?
+            .into())
     }
 }
@@ -2,9 +2,12 @@ use itertools::Itertools;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let (start, finish) = input.trim().split_once('-').unwrap();
-        Box::new(Day::new((start.parse().unwrap(), finish.parse().unwrap())))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        let (start, finish) = input
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("expected a range like 123-456"))?;
+        Ok(Box::new(Day::new((start.parse()?, finish.parse()?))))
     }
 }
 
@@ -21,18 +24,18 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        (self.input.0..=self.input.1)
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok((self.input.0..=self.input.1)
             .into_iter()
             .map(|i| i.to_string())
             .filter(|i| i.chars().tuple_windows().any(|(a, b)| a == b))
             .filter(|i| i.chars().tuple_windows().all(|(a, b)| a <= b))
             .count()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
-        (self.input.0..=self.input.1)
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok((self.input.0..=self.input.1)
             .into_iter()
             .map(|i| {
                 i.to_string()
@@ -48,6 +51,6 @@ impl crate::Day for Day {
             .filter(|i| i.iter().any(|(_, a)| *a == 2))
             .filter(|i| i.iter().tuple_windows().all(|((a, _), (b, _))| a < b))
             .count()
-            .to_string()
+            .into())
     }
 }
@@ -2,8 +2,8 @@ use super::intcode::{Machine, Intcode};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::new(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::new(input)?))
     }
 }
 
@@ -12,25 +12,27 @@ struct Day {
 }
 
 impl Day {
-    pub fn new(input: &str) -> Self {
-        let input = input.parse().unwrap();
-        Self { input }
+    pub fn new(input: &str) -> anyhow::Result<Self> {
+        let input = input.parse()?;
+        Ok(Self { input })
     }
-    pub fn run(&self, input: i64) -> i64 {
+    pub fn run(&self, input: i64) -> anyhow::Result<i64> {
         let mut machine = Machine::from(&self.input[..]);
         machine.input(input);
-        machine.run();
-        machine.last().unwrap()
+        machine.finish()?;
+        machine
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("program produced no output"))
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.run(1i64).to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(1i64)?.into())
     }
 
-    fn part2(&self) -> String {
-        self.run(5i64).to_string()
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(5i64)?.into())
     }
 }
 
@@ -40,8 +42,9 @@ mod tests {
     #[test]
     fn parts_test() {
         use crate::Day as _;
-        let day = Day::new(crate::YEARS[&2019][&5].1);
-        assert_eq!(day.part1(), "9025675".to_string());
-        assert_eq!(day.part2(), "11981754".to_string());
+        let input = crate::inputs::input(2019, 5).unwrap();
+        let day = Day::new(&input).unwrap();
+        assert_eq!(day.part1().unwrap(), crate::Answer::Integer(9025675));
+        assert_eq!(day.part2().unwrap(), crate::Answer::Integer(11981754));
     }
 }
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input<'a>(&'a self, input: &'a str) -> Box<dyn crate::Day + 'a> {
-        Box::new(Day::from_str(input))
+    fn input<'a>(&'a self, input: &'a str) -> anyhow::Result<Box<dyn crate::Day + 'a>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -15,16 +15,17 @@ struct Day<'a> {
 }
 
 impl<'a> Day<'a> {
-    pub fn from_str(input: &'a str) -> Self {
+    pub fn from_str(input: &'a str) -> anyhow::Result<Self> {
         let input = input
             .trim()
             .lines()
-            .filter_map(|s| {
+            .map(|s| {
                 s.split_once(')')
                     .map(|(center, satelite)| (satelite, center))
+                    .ok_or_else(|| anyhow::anyhow!("invalid orbit {:?}", s))
             })
-            .collect();
-        Self { input }
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { input })
     }
 
     pub fn suborbits(&self) -> HashMap<&str, Vec<&str>> {
@@ -46,16 +47,16 @@ impl<'a> Day<'a> {
 }
 
 impl<'a> crate::Day for Day<'a> {
-    fn part1(&self) -> String {
-        self.suborbits().values().map(Vec::len).sum::<usize>().to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.suborbits().values().map(Vec::len).sum::<usize>().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let orbits = self.suborbits();
-        let san = &orbits["SAN"];
-        let you = &orbits["YOU"];
+        let san = orbits.get("SAN").ok_or_else(|| anyhow::anyhow!("SAN is not in any orbit"))?;
+        let you = orbits.get("YOU").ok_or_else(|| anyhow::anyhow!("YOU is not in any orbit"))?;
         let mut i = 0;
         while san[0..=i] == you[0..=i] { i += 1 }
-        (san.len() + you.len() - 2 * i).to_string()
+        Ok((san.len() + you.len() - 2 * i).into())
     }
 }
@@ -5,8 +5,8 @@ use std::{cell::RefCell, ops::ControlFlow};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -15,14 +15,16 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        Self { input: input.parse().unwrap() }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: input.parse()?,
+        })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        (0..=4)
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok((0..=4)
             .permutations(5)
             .map(|phases| {
                 phases
@@ -32,18 +34,22 @@ impl crate::Day for Day {
                         m.input(phase);
                         m
                     })
-                    .fold(0i64, |input, mut amp| {
+                    .try_fold(0i64, |input, mut amp| {
                         amp.input(input);
-                        amp.last().unwrap()
+                        let output = amp.by_ref().last();
+                        amp.check()?;
+                        output.ok_or_else(|| anyhow::anyhow!("amplifier produced no output"))
                     })
             })
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter()
             .max()
-            .unwrap()
-            .to_string()
+            .unwrap_or_default()
+            .into())
     }
 
-    fn part2(&self) -> String {
-        (5i64..=9)
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok((5i64..=9)
             .permutations(5)
             .map(|phases| {
                 let mut amps: Vec<_> = phases
@@ -67,14 +73,17 @@ impl crate::Day for Day {
                         if let Some(n) = amp.next() {
                             ControlFlow::Continue(n)
                         } else {
-                            ControlFlow::Break(input)
+                            ControlFlow::Break(amp.check().map(|()| input))
                         }
                     }) {
-                    ControlFlow::Continue(n) | ControlFlow::Break(n) => n,
+                    ControlFlow::Continue(n) => Ok(n),
+                    ControlFlow::Break(result) => result,
                 }
             })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
             .max()
-            .unwrap()
-            .to_string()
+            .unwrap_or_default()
+            .into())
     }
 }
@@ -8,8 +8,8 @@ use itertools::Itertools as _;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -42,15 +42,19 @@ impl Image {
         x + y * self.width + layer * self.layer_size()
     }
 
-    fn get(&self, x: usize, y: usize) -> char {
+    fn get(&self, x: usize, y: usize) -> anyhow::Result<char> {
         for l in 0..self.layer_len() {
             match self[(x, y, l)] {
-                0 => return ' ',
-                1 => return '█',
+                0 => return Ok(' '),
+                1 => return Ok('█'),
                 _ => (),
             }
         }
-        unimplemented!();
+        Err(anyhow::anyhow!(
+            "pixel ({}, {}) is transparent in every layer",
+            x,
+            y
+        ))
     }
 }
 
@@ -74,16 +78,28 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let input: Vec<_> = input.chars().map(|c| c as u8 - b'0').collect();
-        Self {
-            input: Image::new(&input, 25, 6),
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let input: Vec<_> = input
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|d| d as u8)
+                    .ok_or_else(|| anyhow::anyhow!("invalid pixel {:?}", c))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let input = Image::new(&input, 25, 6);
+        if input.data.is_empty() || !input.data.len().is_multiple_of(input.layer_size()) {
+            return Err(anyhow::anyhow!(
+                "image data of length {} does not fill whole layers",
+                input.data.len()
+            ));
         }
+        Ok(Self { input })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let hist = self
             .input
             .data
@@ -96,15 +112,16 @@ impl crate::Day for Day {
                     map
                 })
             })
-            .min_by_key(|h| h[&0])
-            .unwrap();
-        (hist[&1] * hist[&2]).to_string()
+            .min_by_key(|h| h.get(&0).copied().unwrap_or(0))
+            .ok_or_else(|| anyhow::anyhow!("image has no layers"))?;
+        let count = |pixel| hist.get(&pixel).copied().unwrap_or(0);
+        Ok((count(1) * count(2)).into())
     }
 
-    fn part2(&self) -> String {
-        String::from("\n")
-            + &(0..6)
-                .map(|y| (0..25).map(|x| self.input.get(x, y).to_string()).join(""))
-                .join("\n")
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        let rows = (0..6)
+            .map(|y| (0..25).map(|x| self.input.get(x, y)).collect())
+            .collect::<anyhow::Result<Vec<String>>>()?;
+        Ok(crate::Answer::art(rows))
     }
 }
@@ -2,8 +2,8 @@ use super::intcode::{Intcode, Machine};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -12,23 +12,35 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        Self { input: input.parse().unwrap() }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: input.parse()?,
+        })
     }
-    fn run(&self, input: i64) -> Vec<i64> {
+    fn run(&self, input: i64) -> anyhow::Result<Vec<i64>> {
         let mut machine = Machine::from(&self.input[..]);
         machine.input(input);
-        machine.collect()
+        let output = machine.by_ref().collect();
+        machine.finish()?;
+        Ok(output)
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        format!("{}", self.run(1)[0])
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        let output = self.run(1)?;
+        match output[..] {
+            [keycode] => Ok(keycode.into()),
+            _ => Err(anyhow::anyhow!("BOOST reported faulty opcodes {:?}", output)),
+        }
     }
 
-    fn part2(&self) -> String {
-        format!("{}", self.run(2)[0])
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        let output = self.run(2)?;
+        Ok((*output
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("program produced no output"))?)
+        .into())
     }
 }
 
@@ -38,8 +50,9 @@ mod tests {
 
     #[test]
     fn boost_test() {
-        let day = Day::from_str(crate::YEARS[&2019][&9].1);
-        let output = day.run(1);
+        let input = crate::inputs::input(2019, 9).unwrap();
+        let day = Day::from_str(&input).unwrap();
+        let output = day.run(1).unwrap();
         assert_eq!(output,  [2350741403]);
     }
 }
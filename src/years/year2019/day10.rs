@@ -8,8 +8,8 @@ use std::{
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input<'a>(&self, input: &'a str) -> Box<dyn crate::Day + 'a> {
-        Box::new(Day::from_str(input))
+    fn input<'a>(&self, input: &'a str) -> anyhow::Result<Box<dyn crate::Day + 'a>> {
+        Ok(Box::new(Day::from_str(input)))
     }
 }
 
@@ -33,7 +33,7 @@ impl Day {
         Self { input }
     }
 
-    fn max_vis_asteroid(&self) -> (Point, usize) {
+    fn max_vis_asteroid(&self) -> anyhow::Result<(Point, usize)> {
         self.input
             .iter()
             .copied()
@@ -68,17 +68,23 @@ impl Day {
                 (point, count)
             })
             .max_by_key(|(_, v)| *v)
-            .unwrap()
+            .ok_or_else(|| anyhow::anyhow!("there are no asteroids"))
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.max_vis_asteroid().1.to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.max_vis_asteroid()?.1.into())
     }
 
-    fn part2(&self) -> String {
-        let (asteroid, _) = self.max_vis_asteroid();
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        let (asteroid, _) = self.max_vis_asteroid()?;
+        if self.input.len() <= 200 {
+            return Err(anyhow::anyhow!(
+                "only {} asteroids to vaporize",
+                self.input.len() - 1
+            ));
+        }
 
         let mut by_angle: Vec<Vec<_>> = self
             .input
@@ -107,7 +113,7 @@ impl crate::Day for Day {
             }
         };
 
-        (point.x * 100 + point.y).to_string()
+        Ok((point.x * 100 + point.y).into())
     }
 }
 
@@ -175,7 +181,7 @@ mod tests {
     fn part1_tiny_test() {
         let input = concat![".#..#\n", ".....\n", "#####\n", "....#\n", "...##"];
         let day = Day::from_str(input);
-        assert_eq!(day.part1(), "8");
+        assert_eq!(day.part1().unwrap(), crate::Answer::Integer(8));
     }
     #[test]
     fn part2_large_test() {
@@ -202,6 +208,6 @@ mod tests {
             "###.##.####.##.#..##"
         );
         let day = Day::from_str(input);
-        assert_eq!(day.part2(), "802");
+        assert_eq!(day.part2().unwrap(), crate::Answer::Integer(802));
     }
 }
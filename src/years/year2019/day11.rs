@@ -5,8 +5,8 @@ use super::intcode::{Intcode, Machine};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -72,20 +72,20 @@ impl From<Machine> for Robot {
 }
 
 impl Iterator for Robot {
-    type Item = RobotAction;
+    type Item = anyhow::Result<RobotAction>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let paint = match self.brain.next()? {
             0 => Color::Black,
             1 => Color::White,
-            _ => unreachable!("Invalid Color"),
+            color => return Some(Err(anyhow::anyhow!("invalid color {}", color))),
         };
         let turn = match self.brain.next()? {
             0 => Turn::Left,
             1 => Turn::Right,
-            _ => unreachable!("Invalid Turn"),
+            turn => return Some(Err(anyhow::anyhow!("invalid turn {}", turn))),
         };
-        Some(RobotAction { paint, turn })
+        Some(Ok(RobotAction { paint, turn }))
     }
 }
 
@@ -101,7 +101,7 @@ impl Simulator {
         self.hull.len()
     }
 
-    fn run(&mut self) {
+    fn run(&mut self) -> anyhow::Result<()> {
         loop {
             let last_color = self
                 .hull
@@ -111,7 +111,8 @@ impl Simulator {
                 .unwrap_or(Color::Black);
             self.robot.input(last_color);
             match self.robot.next() {
-                Some(RobotAction { paint, turn }) => {
+                Some(action) => {
+                    let RobotAction { paint, turn } = action?;
                     self.hull
                         .entry(self.pos)
                         .or_insert_with(Vec::new)
@@ -128,6 +129,8 @@ impl Simulator {
                 None => break,
             }
         }
+        self.robot.brain.check()?;
+        Ok(())
     }
 
     fn start(&mut self, color: Color) {
@@ -151,37 +154,36 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        Self { input: input.parse().unwrap() }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: input.parse()?,
+        })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut sim = Simulator::from(&self.input);
-        sim.run();
-        sim.painted().to_string()
+        sim.run()?;
+        Ok(sim.painted().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut sim = Simulator::from(&self.input);
         sim.start(Color::White);
-        sim.run();
+        sim.run()?;
         let final_paint: HashMap<_, _> = sim
             .hull
             .iter()
             .filter_map(|(c, colors)| Some((c, *colors.last()?)))
             .collect();
-        let mut paint_string = String::with_capacity(2 * 6 * 42);
-        for y in 0..6 {
-            paint_string.push('\n');
-            for x in 0..42 {
-                match final_paint.get(&(x, y)).copied().unwrap_or(Color::Black) {
-                    Color::White => paint_string.push('█'),
-                    Color::Black => paint_string.push(' '),
-                }
-            }
-        }
-        paint_string
+        Ok(crate::Answer::art((0..6).map(|y| {
+            (0..42)
+                .map(|x| match final_paint.get(&(x, y)).copied().unwrap_or(Color::Black) {
+                    Color::White => '█',
+                    Color::Black => ' ',
+                })
+                .collect::<String>()
+        })))
     }
 }
@@ -8,8 +8,8 @@ use std::{
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(input.parse::<Day>().unwrap())
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(input.parse::<Day>().map_err(anyhow::Error::msg)?))
     }
 }
 
@@ -110,17 +110,17 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.run(1000).to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(1000).into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let (lengths, starts) = self.loops();
         let length = lengths
             .iter()
             .fold(1usize, |acc, l| num::integer::lcm(acc, *l));
 
-        length.to_string()
+        Ok(length.into())
     }
 }
 
@@ -5,8 +5,8 @@ use super::intcode::{Intcode, Machine};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -15,8 +15,10 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        Self { input: input.parse().unwrap() }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: input.parse()?,
+        })
     }
 }
 
@@ -24,10 +26,11 @@ struct Arcade {
     chip: Machine,
     screen: HashMap<(usize, usize), Tile>,
     points: i64,
+    joystick: i64,
 }
 
 impl Arcade {
-    fn run(&mut self) {
+    fn run(&mut self) -> anyhow::Result<()> {
         while let Some(it) = self.next() {
             match it {
                 Ok((pos, id)) => {
@@ -36,8 +39,10 @@ impl Arcade {
                 Err(points) => self.points = points,
             }
         }
+        self.chip.check()?;
+        Ok(())
     }
-    fn play(&mut self) -> i64 {
+    fn play(&mut self) -> anyhow::Result<i64> {
         self.chip.memory[0] = 2;
         let mut paddle_pos = 0;
         while let Some(it) = self.next() {
@@ -54,7 +59,7 @@ impl Arcade {
                             Ordering::Greater => paddle_pos = 1,
                         }
                     }
-                    self.chip.set_input(vec![paddle_pos]);
+                    self.joystick = paddle_pos;
                 }
                 Err(points) => {
                     self.points = points;
@@ -64,7 +69,22 @@ impl Arcade {
                 }
             }
         }
-        self.points
+        self.chip.check()?;
+        Ok(self.points)
+    }
+
+    /// The next output of the chip, the joystick stays where it is whenever
+    /// the chip asks for it.
+    fn output(&mut self) -> Option<i64> {
+        loop {
+            if let Some(output) = self.chip.next() {
+                return Some(output);
+            }
+            if !self.chip.needs_input() {
+                return None;
+            }
+            self.chip.input(self.joystick);
+        }
     }
 
     #[allow(dead_code)]
@@ -88,9 +108,9 @@ impl Iterator for Arcade {
     type Item = Result<((usize, usize), Tile), i64>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let px = self.chip.next()?;
-        let py = usize::try_from(self.chip.next()?).ok()?;
-        let id = self.chip.next()?;
+        let px = self.output()?;
+        let py = usize::try_from(self.output()?).ok()?;
+        let id = self.output()?;
         if px < 0 {
             return Some(Err(id));
         }
@@ -108,6 +128,7 @@ impl From<&Intcode> for Arcade {
             chip: Machine::from(&i[..]),
             screen: HashMap::new(),
             points: 0,
+            joystick: 0,
         }
     }
 }
@@ -161,19 +182,19 @@ impl TryFrom<i64> for Tile {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut arcade = Arcade::from(&self.input);
-        arcade.run();
-        arcade
+        arcade.run()?;
+        Ok(arcade
             .screen
             .into_values()
             .filter(Tile::is_block)
             .count()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut arcade = Arcade::from(&self.input);
-        arcade.play().to_string()
+        Ok(arcade.play()?.into())
     }
 }
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input<'a>(&self, input: &'a str) -> Box<dyn crate::Day + 'a> {
-        Box::new(Day::from_str(input))
+    fn input<'a>(&self, input: &'a str) -> anyhow::Result<Box<dyn crate::Day + 'a>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -17,10 +17,19 @@ struct Day<'a> {
 }
 
 impl<'a> Day<'a> {
-    pub fn from_str(input: &'a str) -> Self {
-        let (_, recipes) = parsers::recipes(input).unwrap();
-        assert_eq!(recipes.len(), input.lines().count());
-        Self { recipes }
+    pub fn from_str(input: &'a str) -> anyhow::Result<Self> {
+        let (_, recipes) = parsers::recipes(input).map_err(|e| e.to_owned())?;
+        if recipes.len() != input.lines().count() {
+            return Err(anyhow::anyhow!(
+                "parsed {} of {} recipes",
+                recipes.len(),
+                input.lines().count()
+            ));
+        }
+        if !recipes.contains_key(FUEL) {
+            return Err(anyhow::anyhow!("no recipe produces {}", FUEL));
+        }
+        Ok(Self { recipes })
     }
     fn produce(&self, amount: u64) -> u64 {
         let mut to_produce: HashMap<&str, u64> = HashMap::from([(FUEL, amount)]);
@@ -61,11 +70,11 @@ impl<'a> Day<'a> {
 const FUEL: &str = "FUEL";
 const ORE: &str = "ORE";
 impl<'a> crate::Day for Day<'a> {
-    fn part1(&self) -> String {
-        self.produce(1).to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.produce(1).into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         const N_ORE: u64 = 1_000_000_000_000;
         let mut lower = 1;
         let mut upper = 2;
@@ -81,7 +90,7 @@ impl<'a> crate::Day for Day<'a> {
                 lower = middle;
             }
         }
-        lower.to_string()
+        Ok(lower.into())
     }
 }
 
@@ -144,8 +153,8 @@ mod tests {
             "4 C, 1 A => 1 CA\n",
             "2 AB, 3 BC, 4 CA => 1 FUEL\n",
         );
-        let day = Day::from_str(input);
-        assert_eq!("165", day.part1());
+        let day = Day::from_str(input).unwrap();
+        assert_eq!(crate::Answer::Integer(165), day.part1().unwrap());
     }
 
     #[test]
@@ -161,8 +170,8 @@ mod tests {
             "165 ORE => 2 GPVTF\n",
             "3 DCFZ, 7 NZVS, 5 HKGWZ, 10 PSHF => 8 KHKGT\n",
         );
-        let day = Day::from_str(input);
-        assert_eq!("13312", day.part1());
+        let day = Day::from_str(input).unwrap();
+        assert_eq!(crate::Answer::Integer(13312), day.part1().unwrap());
     }
 
     #[test]
@@ -181,7 +190,7 @@ mod tests {
             "1 VJHF, 6 MNCFX => 4 RFSQX\n",
             "176 ORE => 6 VJHF\n",
         );
-        let day = Day::from_str(input);
-        assert_eq!("180697", day.part1());
+        let day = Day::from_str(input).unwrap();
+        assert_eq!(crate::Answer::Integer(180697), day.part1().unwrap());
     }
 }
@@ -5,13 +5,13 @@ use std::{
 };
 
 use super::intcode::{Intcode, Machine};
-use anyhow::{Error, Result};
+use anyhow::{anyhow, Error, Result};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let day: Day = input.try_into().unwrap();
-        Box::new(day)
+    fn input(&self, input: &str) -> Result<Box<dyn crate::Day>> {
+        let day: Day = input.try_into()?;
+        Ok(Box::new(day))
     }
 }
 
@@ -30,16 +30,16 @@ impl TryFrom<&str> for Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> Result<crate::Answer> {
         let mut simulation: Simulation = self.code.clone().into();
-        simulation.explore();
-        simulation.distance_to_oxygen().to_string()
+        simulation.explore()?;
+        Ok(simulation.distance_to_oxygen()?.into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> Result<crate::Answer> {
         let mut simulation: Simulation = self.code.clone().into();
-        simulation.explore();
-        simulation.fill_with_oxygen().to_string()
+        simulation.explore()?;
+        Ok(simulation.fill_with_oxygen()?.into())
     }
 }
 
@@ -60,15 +60,15 @@ impl Plan {
         }
     }
 
-    fn path(&self, from: Coord, to: Coord) -> Vec<Coord> {
+    fn path(&self, from: Coord, to: Coord) -> Result<Vec<Coord>> {
         let mut visited: HashMap<Coord, Vec<Coord>> = HashMap::new();
         let mut candidates = BinaryHeap::new();
         candidates.push((0, from));
         while !visited.contains_key(&to) {
-            if candidates.is_empty() {
-                panic!("no more candidates left but still not at target");
-            }
-            let from = candidates.pop().unwrap().1;
+            let from = candidates
+                .pop()
+                .ok_or_else(|| anyhow!("no path from {:?} to {:?}", from, to))?
+                .1;
             if visited.contains_key(&from) {
                 continue;
             }
@@ -94,7 +94,9 @@ impl Plan {
             }));
         }
 
-        visited.remove(&to).unwrap()
+        visited
+            .remove(&to)
+            .ok_or_else(|| anyhow!("no path from {:?} to {:?}", from, to))
     }
 
     fn update_bounds(&mut self, coord: Coord) {
@@ -126,9 +128,13 @@ impl Plan {
         self.tiles.insert(k, v)
     }
 
-    fn distance_to_oxygen(&self) -> usize {
-        let oxygen = *self.iter().find(|(_, t)| **t == Tile::Oxygen).unwrap().0;
-        self.path((0, 0), oxygen).len() - 1
+    fn distance_to_oxygen(&self) -> Result<usize> {
+        let oxygen = *self
+            .iter()
+            .find(|(_, t)| **t == Tile::Oxygen)
+            .ok_or_else(|| anyhow!("the robot found no oxygen system"))?
+            .0;
+        Ok(self.path((0, 0), oxygen)?.len() - 1)
     }
 
     fn iter(&self) -> std::collections::hash_map::Iter<Coord, Tile> {
@@ -233,11 +239,16 @@ impl Simulation {
         }
     }
 
-    fn try_move(&mut self, direction: Direction) -> bool {
+    fn try_move(&mut self, direction: Direction) -> Result<bool> {
         self.robot.input(direction.to_command());
+        self.robot.run()?;
+        let status = match self.robot.output()[..] {
+            [status] => status,
+            ref output => return Err(anyhow!("the robot answered a move with {:?}", output)),
+        };
         let target = direction.apply(self.pos);
-        match Tile::from(self.robot.next().unwrap()) {
-            Tile::Unknown => unreachable!(),
+        Ok(match Tile::from(status) {
+            Tile::Unknown => return Err(anyhow!("the robot reported the status {}", status)),
             tile @ Tile::Wall => {
                 self.plan.insert(target, tile);
                 false
@@ -247,38 +258,44 @@ impl Simulation {
                 self.plan.insert(target, tile);
                 true
             }
-        }
+        })
     }
 
-    fn explore(&mut self) {
+    fn explore(&mut self) -> Result<()> {
         let mut left_unknown: Vec<Coord> = self.plan.unknown_neighbours((0, 0)).collect();
         while let Some(target) = left_unknown.pop() {
             if self.plan.contains_key(&target) {
                 continue;
             }
             let target = target;
-            let neighbour = self.plan.walkable_neighbours(target).next().unwrap();
-            let path = self.plan.path(self.pos, neighbour);
+            let neighbour = self
+                .plan
+                .walkable_neighbours(target)
+                .next()
+                .ok_or_else(|| anyhow!("no walkable tile next to {:?}", target))?;
+            let path = self.plan.path(self.pos, neighbour)?;
             let directions = self.path_to_directions(&path);
             let mut i = 1;
             for direction in directions {
-                if !self.try_move(direction) {
-                    panic!("could not move there {:?}", direction);
+                if !self.try_move(direction)? {
+                    return Err(anyhow!("could not move there {:?}", direction));
                 }
                 if self.pos != path[i] {
-                    panic!(
+                    return Err(anyhow!(
                         "should be here {:?}, am here instead {:?}",
-                        path[i], self.pos
-                    );
+                        path[i],
+                        self.pos
+                    ));
                 }
                 i += 1;
             }
-            self.try_move(Direction::from([neighbour, target]));
+            self.try_move(Direction::from([neighbour, target]))?;
             left_unknown.extend(self.plan.unknown_neighbours(self.pos));
         }
+        Ok(())
     }
 
-    fn fill_with_oxygen(&mut self) -> usize {
+    fn fill_with_oxygen(&mut self) -> Result<usize> {
         let mut i = 0;
         let mut oxys: Vec<_> = self
             .plan
@@ -289,6 +306,9 @@ impl Simulation {
             .collect();
 
         while self.plan.values().any(|x| *x == Tile::Empty) {
+            if oxys.is_empty() {
+                return Err(anyhow!("the oxygen can't reach every empty tile"));
+            }
             let mut next_oxys = Vec::new();
             for oxy in &oxys {
                 for prev_empty in self
@@ -304,14 +324,14 @@ impl Simulation {
             oxys = next_oxys;
             i += 1;
         }
-        i
+        Ok(i)
     }
 
     fn path_to_directions(&self, path: &[Coord]) -> Vec<Direction> {
         path.windows(2).map(Direction::from).collect()
     }
 
-    fn distance_to_oxygen(&self) -> usize {
+    fn distance_to_oxygen(&self) -> Result<usize> {
         self.plan.distance_to_oxygen()
     }
 }
@@ -1,8 +1,8 @@
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -13,9 +13,16 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let input = input.chars().map(|c| c as i64 - '0' as i64).collect();
-        Self { input }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let input = input
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(i64::from)
+                    .ok_or_else(|| anyhow::anyhow!("invalid digit {:?}", c))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { input })
     }
 }
 
@@ -51,19 +58,19 @@ fn step(numbers: &[i64], offset: usize) -> Vec<i64> {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut numbers = self.input.to_vec();
         for _ in 0..100 {
             numbers = step(&numbers, 0);
         }
-        numbers
+        Ok(numbers
             .iter()
             .take(8)
             .fold(0, |acc, n| acc * 10 + n)
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let offset = self
             .input
             .iter()
@@ -76,16 +83,18 @@ impl crate::Day for Day {
             .copied()
             .cycle()
             .take(self.input.len() * 10000);
-        numbers.advance_by(offset).unwrap();
+        numbers
+            .advance_by(offset)
+            .map_err(|_| anyhow::anyhow!("message offset {} is out of bounds", offset))?;
         let mut numbers: Vec<_> = numbers.collect();
         for _ in 0..100 {
             numbers = step(&numbers, offset);
         }
 
-        numbers
+        Ok(numbers
             .iter()
             .take(8)
             .fold(0, |acc, n| acc * 10 + n)
-            .to_string()
+            .into())
     }
 }
@@ -3,8 +3,8 @@ use super::intcode::{Intcode, Machine};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -13,19 +13,19 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let input = input.parse().unwrap();
-        Self { input }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let input = input.parse()?;
+        Ok(Self { input })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        let m = Machine::from(&self.input);
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        let mut m = Machine::from(&self.input);
+        let view: String = m.by_ref().map(|x| x as u8 as char).collect();
+        m.finish()?;
         let mut width = 0;
-        let board: Vec<_> = m
-            .map(|x| x as u8 as char)
-            .collect::<String>()
+        let board: Vec<_> = view
             .lines()
             .flat_map(|l| {
                 if width == 0 {
@@ -65,10 +65,10 @@ impl crate::Day for Day {
                 }
             }
         }
-        checksum.to_string()
+        Ok(checksum.into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let main = "A,B,A,C,B,C,A,C,B,C";
         let a = "L,8,R,10,L,10";
         let b = "R,10,L,8,L,8,L,10";
@@ -83,7 +83,11 @@ impl crate::Day for Day {
                 .copied()
                 .map(i64::from),
         );
-        m.last().unwrap().to_string()
+        let dust = m.by_ref().last();
+        m.finish()?;
+        Ok(dust
+            .ok_or_else(|| anyhow::anyhow!("the robot did not report any dust"))?
+            .into())
     }
 }
 
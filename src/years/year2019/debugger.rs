@@ -0,0 +1,396 @@
+use super::intcode::{self, Line, Machine, State, Tracer};
+use anyhow::{anyhow, Context, Result};
+use std::{
+    collections::BTreeSet,
+    fs,
+    io::{BufRead, Write},
+};
+
+const HELP: &str = "\
+step [n]          runs the next n instructions (1)
+continue          runs until a breakpoint, watchpoint, missing input, fault or halt
+break [address]   stops before the instruction at address, lists the breakpoints without one
+clear address     removes a breakpoint
+watch [address]   stops after a write to address, lists the watchpoints without one
+unwatch address   removes a watchpoint
+input n...        queues numbers as input
+ascii text        queues text and a newline as input
+output            prints and clears the output so far, as text too if it is ASCII
+regs              prints ip, relative_base and the pending input
+mem address [n]   prints n words of memory from address (8)
+set address value changes a word of memory
+dis [address] [n] disassembles n lines from address (ip, 10)
+save file         saves a snapshot of the machine to file
+load file         restores the machine from a snapshot
+quit              leaves the debugger";
+
+/// How many words `mem` prints and `set` adds past the end of memory at most,
+/// larger requests are most likely typos.
+const MAX_WORDS: usize = 1 << 20;
+
+/// Remembers the writes to the watched addresses.
+#[derive(Debug, Default)]
+pub struct Watcher {
+    pub cells: BTreeSet<usize>,
+    hits: Vec<(usize, i64)>,
+}
+
+impl Tracer for Watcher {
+    fn write(&mut self, address: usize, value: i64) {
+        if self.cells.contains(&address) {
+            self.hits.push((address, value));
+        }
+    }
+}
+
+pub struct Debugger {
+    machine: Machine<Watcher>,
+    breakpoints: BTreeSet<usize>,
+}
+
+impl Debugger {
+    pub fn new(machine: Machine) -> Self {
+        Self {
+            machine: machine.with_tracer(Watcher::default()),
+            breakpoints: BTreeSet::new(),
+        }
+    }
+
+    /// Reads commands from `input` until it ends or `quit`, errors of single
+    /// commands are printed and don't end the session.
+    pub fn repl(&mut self, mut input: impl BufRead, mut output: impl Write) -> Result<()> {
+        writeln!(output, "type `help` for the commands")?;
+        self.print_next(&mut output)?;
+        let mut line = String::new();
+        loop {
+            write!(output, "(intcode) ")?;
+            output.flush()?;
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                return Ok(());
+            }
+            match line.trim() {
+                "" => {}
+                "quit" | "q" => return Ok(()),
+                command => {
+                    if let Err(e) = self.command(command, &mut output) {
+                        writeln!(output, "error: {:#}", e)?;
+                    }
+                }
+            }
+        }
+    }
+
+    fn command(&mut self, command: &str, out: &mut impl Write) -> Result<()> {
+        let (name, rest) = command.split_once(' ').unwrap_or((command, ""));
+        let args: Vec<_> = rest.split_whitespace().collect();
+        let number = |i: usize| -> Result<i64> {
+            let arg = args
+                .get(i)
+                .ok_or_else(|| anyhow!("{} needs more arguments", name))?;
+            arg.parse()
+                .with_context(|| format!("{:?} is not a number", arg))
+        };
+        let address = |i: usize| -> Result<usize> {
+            let n = number(i)?;
+            usize::try_from(n).map_err(|_| anyhow!("{} is not an address", n))
+        };
+        match name {
+            "help" | "h" => writeln!(out, "{}", HELP)?,
+            "step" | "s" => {
+                let n = if args.is_empty() { 1 } else { address(0)? };
+                for _ in 0..n {
+                    if !self.step(out)? {
+                        break;
+                    }
+                }
+                self.print_next(out)?;
+            }
+            "continue" | "c" => {
+                // leave the breakpoint we are standing on
+                if self.step(out)? {
+                    while self.step(out)? && !self.breakpoints.contains(&self.machine.ip()) {}
+                }
+                self.print_next(out)?;
+            }
+            "break" | "b" if args.is_empty() => list(out, "breakpoints", &self.breakpoints)?,
+            "break" | "b" => {
+                self.breakpoints.insert(address(0)?);
+            }
+            "clear" => {
+                if !self.breakpoints.remove(&address(0)?) {
+                    return Err(anyhow!("there is no breakpoint at {}", args[0]));
+                }
+            }
+            "watch" | "w" if args.is_empty() => {
+                list(out, "watchpoints", &self.machine.tracer().cells)?
+            }
+            "watch" | "w" => {
+                self.machine.tracer_mut().cells.insert(address(0)?);
+            }
+            "unwatch" => {
+                if !self.machine.tracer_mut().cells.remove(&address(0)?) {
+                    return Err(anyhow!("there is no watchpoint at {}", args[0]));
+                }
+            }
+            "input" | "i" => {
+                let numbers = (0..args.len()).map(number).collect::<Result<Vec<_>>>()?;
+                self.machine.input_iter(numbers);
+            }
+            "ascii" => self
+                .machine
+                .input_iter(rest.bytes().chain(*b"\n").map(i64::from)),
+            "output" | "o" => {
+                let output = self.machine.output();
+                let numbers: Vec<_> = output.iter().map(i64::to_string).collect();
+                writeln!(out, "{}", numbers.join(", "))?;
+                let text: Option<String> = output
+                    .iter()
+                    .map(|&n| {
+                        u8::try_from(n)
+                            .ok()
+                            .filter(|b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+                            .map(char::from)
+                    })
+                    .collect();
+                if let Some(text) = text.filter(|text| !text.is_empty()) {
+                    writeln!(out, "{}", text.trim_end())?;
+                }
+            }
+            "regs" | "r" => {
+                let input: Vec<_> = self
+                    .machine
+                    .pending_input()
+                    .iter()
+                    .map(i64::to_string)
+                    .collect();
+                writeln!(
+                    out,
+                    "ip {}  relative_base {}  input [{}]{}",
+                    self.machine.ip(),
+                    self.machine.relative_base(),
+                    input.join(", "),
+                    if self.machine.halt { "  halted" } else { "" }
+                )?;
+            }
+            "mem" | "x" => {
+                let start = address(0)?;
+                let n = if args.len() > 1 { address(1)? } else { 8 };
+                if n > MAX_WORDS {
+                    return Err(anyhow!("mem prints at most {} words", MAX_WORDS));
+                }
+                let end = start
+                    .checked_add(n)
+                    .ok_or_else(|| anyhow!("{} words from {} are past any address", n, start))?;
+                let words: Vec<_> = (start..end)
+                    .map(|address| self.machine.memory.get(address).copied().unwrap_or(0))
+                    .map(|word| word.to_string())
+                    .collect();
+                writeln!(out, "{}: {}", start, words.join(" "))?;
+            }
+            "set" => {
+                let (address, value) = (address(0)?, number(1)?);
+                if address.saturating_sub(self.machine.memory.len()) >= MAX_WORDS {
+                    return Err(anyhow!(
+                        "{} is more than {} words past the end of memory",
+                        address,
+                        MAX_WORDS
+                    ));
+                }
+                if address >= self.machine.memory.len() {
+                    self.machine.memory.resize(address + 1, 0);
+                }
+                self.machine.memory[address] = value;
+            }
+            "dis" | "d" => {
+                let start = if args.is_empty() {
+                    self.machine.ip()
+                } else {
+                    address(0)?
+                };
+                let n = if args.len() > 1 { address(1)? } else { 10 };
+                let mut address = start;
+                for _ in 0..n {
+                    let line = match intcode::decode_at(&self.machine.memory, address) {
+                        Some(line) => line,
+                        None => match self.machine.memory.get(address) {
+                            Some(&word) => Line::Data {
+                                address,
+                                words: vec![word],
+                            },
+                            None => break,
+                        },
+                    };
+                    self.print_line(out, &line)?;
+                    address += line.words().len();
+                }
+            }
+            "save" => {
+                let path = args.first().ok_or_else(|| anyhow!("save needs a file"))?;
+                fs::write(path, self.machine.snapshot())
+                    .with_context(|| format!("could not write {}", path))?;
+            }
+            "load" => {
+                let path = args.first().ok_or_else(|| anyhow!("load needs a file"))?;
+                let snapshot =
+                    fs::read_to_string(path).with_context(|| format!("could not read {}", path))?;
+                self.machine
+                    .restore(&snapshot)
+                    .with_context(|| format!("invalid snapshot in {}", path))?;
+                self.print_next(out)?;
+            }
+            _ => return Err(anyhow!("unknown command {:?}, try `help`", name)),
+        }
+        Ok(())
+    }
+
+    /// Runs one instruction unless the machine can't, returns whether it ran
+    /// and nothing was written to a watched address.
+    fn step(&mut self, out: &mut impl Write) -> Result<bool> {
+        if self.machine.halt {
+            writeln!(out, "the machine halted")?;
+            return Ok(false);
+        }
+        match self.machine.step() {
+            Ok(State::Running | State::Output(_)) => {}
+            Ok(State::NeedsInput) => {
+                writeln!(out, "waiting for input, queue some with `input` or `ascii`")?;
+                return Ok(false);
+            }
+            Ok(State::Halted) => {
+                writeln!(out, "the machine halted")?;
+                return Ok(false);
+            }
+            Err(fault) => {
+                writeln!(out, "fault: {}", fault)?;
+                return Ok(false);
+            }
+        }
+        let hits = std::mem::take(&mut self.machine.tracer_mut().hits);
+        for (address, value) in &hits {
+            writeln!(out, "watchpoint: {} = {}", address, value)?;
+        }
+        Ok(hits.is_empty())
+    }
+
+    fn print_next(&self, out: &mut impl Write) -> Result<()> {
+        if self.machine.halt {
+            return Ok(());
+        }
+        match self.machine.next_instruction() {
+            Some(line) => self.print_line(out, &line),
+            None => Ok(writeln!(out, "{:>6}: ?", self.machine.ip())?),
+        }
+    }
+
+    /// The line with a `>` if it is the next instruction and a `*` if it has
+    /// a breakpoint.
+    fn print_line(&self, out: &mut impl Write, line: &Line) -> Result<()> {
+        let address = line.address();
+        writeln!(
+            out,
+            "{}{}{:>4}: {}",
+            if address == self.machine.ip() {
+                '>'
+            } else {
+                ' '
+            },
+            if self.breakpoints.contains(&address) {
+                '*'
+            } else {
+                ' '
+            },
+            address,
+            line
+        )?;
+        Ok(())
+    }
+}
+
+fn list(out: &mut impl Write, name: &str, addresses: &BTreeSet<usize>) -> Result<()> {
+    if addresses.is_empty() {
+        writeln!(out, "no {}", name)?;
+    } else {
+        let addresses: Vec<_> = addresses.iter().map(usize::to_string).collect();
+        writeln!(out, "{}: {}", name, addresses.join(", "))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(program: &str, commands: &str) -> String {
+        let program = intcode::assemble(program).unwrap();
+        let mut output = Vec::new();
+        Debugger::new(Machine::from(program))
+            .repl(commands.as_bytes(), &mut output)
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn repl_test() {
+        let program = "
+            loop: in x
+                  jf x, #end
+                  out x
+                  jt #1, #loop
+            end:  hcf
+            x:    data 0
+        ";
+        let output = session(
+            program,
+            "break 5\ncontinue\ninput 3 0\ncontinue\nregs\nwatch 11\ncontinue\n\
+             output\ncontinue\ncontinue\nregs\nbogus\n",
+        );
+        let expected = [
+            "type `help` for the commands",
+            ">    0: in 11",
+            "(intcode) (intcode) waiting for input, queue some with `input` or `ascii`",
+            ">    0: in 11",
+            "(intcode) (intcode) >*   5: out 11",
+            "(intcode) ip 5  relative_base 0  input [0]",
+            "(intcode) (intcode) watchpoint: 11 = 0",
+            ">    2: jf 11, #10",
+            "(intcode) 3",
+            "(intcode) the machine halted",
+            "(intcode) the machine halted",
+            "(intcode) ip 10  relative_base 0  input []  halted",
+            "(intcode) error: unknown command \"bogus\", try `help`",
+            "(intcode) ",
+        ];
+        assert_eq!(output.split('\n').collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn snapshot_test() {
+        let path = std::env::temp_dir().join(format!("aoc-debugger-{}.json", std::process::id()));
+        let commands = format!(
+            "ascii A\nstep 2\nsave {0}\nstep\nload {0}\nregs\nmem 0 3\nset 1 7\nmem 1 1\n",
+            path.display()
+        );
+        let output = session("in 9\nin 9\nhcf", &commands);
+        std::fs::remove_file(&path).unwrap();
+        assert!(output.contains("ip 4  relative_base 0  input []\n"));
+        assert!(output.contains("0: 3 9 3\n"));
+        assert!(output.contains("1: 7\n"));
+    }
+
+    #[test]
+    fn bounds_test() {
+        let output = session(
+            "hcf",
+            "mem 0 9999999
+set 9999999 1
+mem 0 2
+",
+        );
+        assert!(output.contains("error: mem prints at most 1048576 words\n"));
+        assert!(
+            output.contains("error: 9999999 is more than 1048576 words past the end of memory\n")
+        );
+        assert!(output.contains("0: 99 0\n"));
+    }
+}
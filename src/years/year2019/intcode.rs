@@ -1,7 +1,9 @@
-use anyhow::{Error, Result};
+use anyhow::{anyhow, Context, Error, Result};
 use std::{
-    collections::VecDeque,
-    fmt::Debug,
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    fmt::{self, Debug},
+    io,
     iter::IntoIterator,
     ops::{Deref, DerefMut, Index, IndexMut},
     str::FromStr,
@@ -44,26 +46,36 @@ impl DerefMut for Intcode {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum PMode {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PMode {
     Immediate,
     Positional,
     Relative,
 }
 
-impl From<u8> for PMode {
-    fn from(mode: u8) -> Self {
+impl PMode {
+    fn decode(mode: u8) -> Option<Self> {
         match mode {
-            0 => Self::Positional,
-            1 => Self::Immediate,
-            2 => Self::Relative,
-            _ => unimplemented!("invalid parameter mode"),
+            0 => Some(Self::Positional),
+            1 => Some(Self::Immediate),
+            2 => Some(Self::Relative),
+            _ => None,
+        }
+    }
+
+    /// `value` as an operand, `#value` if it's immediate and `[value]` if
+    /// it's relative.
+    fn operand(self, value: Atom) -> String {
+        match self {
+            Self::Immediate => format!("#{}", value),
+            Self::Positional => value.to_string(),
+            Self::Relative => format!("[{}]", value),
         }
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum OpCode {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OpCode {
     Add(PMode, PMode, PMode),
     Mul(PMode, PMode, PMode),
     Input(PMode),
@@ -85,177 +97,846 @@ impl OpCode {
             Self::JumpIfTrue(..) | Self::JumpIfFalse(..) => 3,
         }
     }
+
+    /// Decodes an instruction word, `None` if it isn't one.
+    pub fn decode(word: Atom) -> Option<Self> {
+        let mode = |n: u32| PMode::decode(u8::try_from(word / 10i64.pow(n + 1) % 10).ok()?);
+        let op_code = match word % 100 {
+            1 => Self::Add(mode(1)?, mode(2)?, mode(3)?),
+            2 => Self::Mul(mode(1)?, mode(2)?, mode(3)?),
+            3 => Self::Input(mode(1)?),
+            4 => Self::Output(mode(1)?),
+            5 => Self::JumpIfTrue(mode(1)?, mode(2)?),
+            6 => Self::JumpIfFalse(mode(1)?, mode(2)?),
+            7 => Self::LessThan(mode(1)?, mode(2)?, mode(3)?),
+            8 => Self::Equals(mode(1)?, mode(2)?, mode(3)?),
+            9 => Self::AdjustRelativeBase(mode(1)?),
+            99 => Self::Halt,
+            _ => return None,
+        };
+        // modes beyond the parameters must be 0
+        (word >= 0 && word / 10i64.pow(op_code.modes().len() as u32 + 2) == 0).then_some(op_code)
+    }
+
+    /// The instruction called `mnemonic` with parameters in `modes`, `None`
+    /// if there is none or it takes a different number of parameters.
+    pub fn from_mnemonic(mnemonic: &str, modes: &[PMode]) -> Option<Self> {
+        Some(match (mnemonic, modes) {
+            ("add", &[a, b, c]) => Self::Add(a, b, c),
+            ("mul", &[a, b, c]) => Self::Mul(a, b, c),
+            ("in", &[a]) => Self::Input(a),
+            ("out", &[a]) => Self::Output(a),
+            ("jt", &[a, b]) => Self::JumpIfTrue(a, b),
+            ("jf", &[a, b]) => Self::JumpIfFalse(a, b),
+            ("lt", &[a, b, c]) => Self::LessThan(a, b, c),
+            ("eq", &[a, b, c]) => Self::Equals(a, b, c),
+            ("adj", &[a]) => Self::AdjustRelativeBase(a),
+            ("hcf", &[]) => Self::Halt,
+            _ => return None,
+        })
+    }
+
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            Self::Add(..) => "add",
+            Self::Mul(..) => "mul",
+            Self::Input(_) => "in",
+            Self::Output(_) => "out",
+            Self::JumpIfTrue(..) => "jt",
+            Self::JumpIfFalse(..) => "jf",
+            Self::LessThan(..) => "lt",
+            Self::Equals(..) => "eq",
+            Self::AdjustRelativeBase(_) => "adj",
+            Self::Halt => "hcf",
+        }
+    }
+
+    /// The modes of the parameters.
+    pub fn modes(&self) -> Vec<PMode> {
+        match *self {
+            Self::Add(a, b, c)
+            | Self::Mul(a, b, c)
+            | Self::LessThan(a, b, c)
+            | Self::Equals(a, b, c) => {
+                vec![a, b, c]
+            }
+            Self::JumpIfTrue(a, b) | Self::JumpIfFalse(a, b) => vec![a, b],
+            Self::Input(a) | Self::Output(a) | Self::AdjustRelativeBase(a) => vec![a],
+            Self::Halt => vec![],
+        }
+    }
+}
+
+/// A part of a disassembled program.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Line {
+    Instruction {
+        address: usize,
+        op_code: OpCode,
+        params: Vec<Atom>,
+    },
+    /// words that don't decode to an instruction
+    Data { address: usize, words: Vec<Atom> },
+}
+
+impl Line {
+    pub fn address(&self) -> usize {
+        match self {
+            Line::Instruction { address, .. } | Line::Data { address, .. } => *address,
+        }
+    }
+
+    /// The words the line was decoded from.
+    pub fn words(&self) -> Vec<Atom> {
+        match self {
+            Line::Instruction {
+                op_code, params, ..
+            } => {
+                let mut words = vec![encode(op_code)];
+                words.extend(params);
+                words
+            }
+            Line::Data { words, .. } => words.clone(),
+        }
+    }
+
+    /// The address an instruction jumps to if that is known without running it.
+    pub fn jump_target(&self) -> Option<usize> {
+        match self {
+            Line::Instruction {
+                op_code: OpCode::JumpIfTrue(_, target) | OpCode::JumpIfFalse(_, target),
+                params,
+                ..
+            } if *target == PMode::Immediate => usize::try_from(params[1]).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// The instruction word of `op_code`.
+fn encode(op_code: &OpCode) -> Atom {
+    let code = match op_code {
+        OpCode::Add(..) => 1,
+        OpCode::Mul(..) => 2,
+        OpCode::Input(_) => 3,
+        OpCode::Output(_) => 4,
+        OpCode::JumpIfTrue(..) => 5,
+        OpCode::JumpIfFalse(..) => 6,
+        OpCode::LessThan(..) => 7,
+        OpCode::Equals(..) => 8,
+        OpCode::AdjustRelativeBase(_) => 9,
+        OpCode::Halt => 99,
+    };
+    op_code
+        .modes()
+        .iter()
+        .zip([100, 1000, 10000])
+        .map(|(mode, factor)| {
+            factor
+                * match mode {
+                    PMode::Positional => 0,
+                    PMode::Immediate => 1,
+                    PMode::Relative => 2,
+                }
+        })
+        .sum::<Atom>()
+        + code
+}
+
+/// The instruction or data in assembly, e.g. `mul 4, #3, [2]` or `data 33`.
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (mnemonic, operands): (_, Vec<_>) = match self {
+            Line::Instruction {
+                op_code, params, ..
+            } => (
+                op_code.mnemonic(),
+                op_code
+                    .modes()
+                    .into_iter()
+                    .zip(params)
+                    .map(|(mode, &value)| mode.operand(value))
+                    .collect(),
+            ),
+            Line::Data { words, .. } => ("data", words.iter().map(Atom::to_string).collect()),
+        };
+        f.write_str(mnemonic)?;
+        if !operands.is_empty() {
+            write!(f, " {}", operands.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The instruction at `address`, `None` if there is none or its parameters
+/// run past the end.
+pub fn decode_at(code: &[Atom], address: usize) -> Option<Line> {
+    let op_code = OpCode::decode(*code.get(address)?)?;
+    let params = code.get(address + 1..address + 1 + op_code.modes().len())?;
+    Some(Line::Instruction {
+        address,
+        op_code,
+        params: params.to_vec(),
+    })
+}
+
+/// Decodes `code` front to back, words that aren't an instruction or whose
+/// parameters run past the end are data.
+pub fn disassemble(code: &[Atom]) -> Vec<Line> {
+    const MAX_DATA: usize = 8;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut address = 0;
+    while address < code.len() {
+        match (decode_at(code, address), lines.last_mut()) {
+            (Some(instruction), _) => {
+                address += instruction.words().len();
+                lines.push(instruction);
+            }
+            (None, Some(Line::Data { words, .. })) if words.len() < MAX_DATA => {
+                words.push(code[address]);
+                address += 1;
+            }
+            (None, _) => {
+                lines.push(Line::Data {
+                    address,
+                    words: vec![code[address]],
+                });
+                address += 1;
+            }
+        }
+    }
+    lines
+}
+
+/// The disassembly of `code` in a form [`assemble`] turns back into `code`.
+pub fn source(code: &[Atom]) -> String {
+    disassemble(code)
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect()
+}
+
+/// An operand before its labels are resolved.
+enum Operand<'a> {
+    Number(Atom),
+    Label(&'a str),
+}
+
+/// Assembles a program written like the output of [`source`], e.g.
+///
+/// ```text
+/// loop: in x         ; read a number
+///       jf x, #end
+///       out x
+///       jt #1, #loop
+/// end:  hcf
+/// x:    data 0
+/// ```
+///
+/// Operands are positional, `#immediate` or `[relative]`. Labels stand for
+/// the address of what follows them.
+pub fn assemble(source: &str) -> Result<Intcode> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    let mut address = 0;
+    for (number, line) in (1..).zip(source.lines()) {
+        let context = || format!("line {}", number);
+        let mut line = line.split(';').next().unwrap().trim();
+        while let Some((label, rest)) = line.split_once(':') {
+            let label = label.trim();
+            if !is_label(label) {
+                return Err(anyhow!("invalid label {:?}", label)).with_context(context);
+            }
+            if labels.insert(label, address).is_some() {
+                return Err(anyhow!("{} is defined twice", label)).with_context(context);
+            }
+            line = rest.trim();
+        }
+        if line.is_empty() {
+            continue;
+        }
+        let (mnemonic, operands) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let operands = operands
+            .split(',')
+            .map(str::trim)
+            .filter(|operand| !operand.is_empty())
+            .map(parse_operand)
+            .collect::<Result<Vec<_>>>()
+            .with_context(context)?;
+        let words: Vec<_> = if mnemonic == "data" {
+            if operands.is_empty() {
+                return Err(anyhow!("data needs a value")).with_context(context);
+            }
+            operands.into_iter().map(|(_, operand)| operand).collect()
+        } else {
+            let modes: Vec<_> = operands.iter().map(|&(mode, _)| mode).collect();
+            let op_code = OpCode::from_mnemonic(mnemonic, &modes)
+                .ok_or_else(|| match arity(mnemonic) {
+                    Some(n) => anyhow!("{} takes {} operands, not {}", mnemonic, n, modes.len()),
+                    None => anyhow!("unknown mnemonic {:?}", mnemonic),
+                })
+                .with_context(context)?;
+            [Operand::Number(encode(&op_code))]
+                .into_iter()
+                .chain(operands.into_iter().map(|(_, operand)| operand))
+                .collect()
+        };
+        address += words.len();
+        statements.push((number, words));
+    }
+    let mut code = Vec::with_capacity(address);
+    for (number, words) in statements {
+        for word in words {
+            code.push(match word {
+                Operand::Number(value) => value,
+                Operand::Label(label) => *labels
+                    .get(label)
+                    .ok_or_else(|| anyhow!("line {}: {} is not defined", number, label))?
+                    as Atom,
+            });
+        }
+    }
+    Ok(code.into())
 }
 
+/// How many operands the instruction called `mnemonic` takes.
+fn arity(mnemonic: &str) -> Option<usize> {
+    (0..=3).find(|&n| OpCode::from_mnemonic(mnemonic, &[PMode::Positional; 3][..n]).is_some())
+}
+
+fn is_label(label: &str) -> bool {
+    label.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `5`, `#5` or `[5]`, each with a label instead of the number if wanted.
+fn parse_operand(operand: &str) -> Result<(PMode, Operand<'_>)> {
+    let (mode, value) = if let Some(value) = operand.strip_prefix('#') {
+        (PMode::Immediate, value)
+    } else if let Some(value) = operand.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        (PMode::Relative, value)
+    } else {
+        (PMode::Positional, operand)
+    };
+    let value = value.trim();
+    let value = match value.parse() {
+        Ok(number) => Operand::Number(number),
+        Err(_) if is_label(value) => Operand::Label(value),
+        Err(_) => return Err(anyhow!("invalid operand {:?}", operand)),
+    };
+    Ok((mode, value))
+}
+
+/// A listing of `code` with the address and words of every line, addresses
+/// that are the target of a jump are marked with `>`.
+pub fn listing(code: &[Atom]) -> String {
+    let lines = disassemble(code);
+    let targets: BTreeSet<_> = lines.iter().filter_map(Line::jump_target).collect();
+    let width = code.len().to_string().len();
+    lines
+        .iter()
+        .map(|line| {
+            let words: Vec<_> = line.words().iter().map(Atom::to_string).collect();
+            let marker = if targets.contains(&line.address()) {
+                '>'
+            } else {
+                ' '
+            };
+            format!(
+                "{}{:>width$}: {:<24} {}\n",
+                marker,
+                line.address(),
+                words.join(" "),
+                line,
+                width = width
+            )
+        })
+        .collect()
+}
+
+/// Gets told what a [`Machine`] does, the default `()` ignores everything
+/// and is optimized away entirely.
+pub trait Tracer {
+    /// The instruction at `ip` is about to run, `params` are its raw
+    /// parameters.
+    fn instruction(&mut self, _ip: usize, _op_code: OpCode, _params: &[Atom]) {}
+    /// A parameter was read from memory.
+    fn read(&mut self, _address: usize, _value: Atom) {}
+    /// An instruction wrote to memory.
+    fn write(&mut self, _address: usize, _value: Atom) {}
+}
+
+impl Tracer for () {}
+
+/// Both tracers see everything.
+impl<A: Tracer, B: Tracer> Tracer for (A, B) {
+    fn instruction(&mut self, ip: usize, op_code: OpCode, params: &[Atom]) {
+        self.0.instruction(ip, op_code, params);
+        self.1.instruction(ip, op_code, params);
+    }
+    fn read(&mut self, address: usize, value: Atom) {
+        self.0.read(address, value);
+        self.1.read(address, value);
+    }
+    fn write(&mut self, address: usize, value: Atom) {
+        self.0.write(address, value);
+        self.1.write(address, value);
+    }
+}
+
+impl<T: Tracer + ?Sized> Tracer for Box<T> {
+    fn instruction(&mut self, ip: usize, op_code: OpCode, params: &[Atom]) {
+        (**self).instruction(ip, op_code, params);
+    }
+    fn read(&mut self, address: usize, value: Atom) {
+        (**self).read(address, value);
+    }
+    fn write(&mut self, address: usize, value: Atom) {
+        (**self).write(address, value);
+    }
+}
+
+/// Writes every instruction and memory access to `W`, stdout by default:
+///
+/// ```text
+///    22: mul 21, #125, 20
+///        read 21 = 8
+///        write 20 = 1000
+/// ```
 #[derive(Debug)]
-pub struct Machine {
+pub struct Logger<W = io::Stdout> {
+    writer: W,
+}
+
+impl Logger {
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+impl<W: io::Write> Logger<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+// a log that can't be written shouldn't stop the machine
+impl<W: io::Write> Tracer for Logger<W> {
+    fn instruction(&mut self, ip: usize, op_code: OpCode, params: &[Atom]) {
+        let line = Line::Instruction {
+            address: ip,
+            op_code,
+            params: params.to_vec(),
+        };
+        let _ = writeln!(self.writer, "{:5}: {}", ip, line);
+    }
+    fn read(&mut self, address: usize, value: Atom) {
+        let _ = writeln!(self.writer, "       read {} = {}", address, value);
+    }
+    fn write(&mut self, address: usize, value: Atom) {
+        let _ = writeln!(self.writer, "       write {} = {}", address, value);
+    }
+}
+
+/// Counts the instructions that ran, by address and by mnemonic.
+#[derive(Clone, Debug, Default)]
+pub struct Profiler {
+    pub by_address: BTreeMap<usize, u64>,
+    pub by_mnemonic: BTreeMap<&'static str, u64>,
+}
+
+impl Profiler {
+    pub fn total(&self) -> u64 {
+        self.by_mnemonic.values().sum()
+    }
+
+    /// The `n` addresses that ran most often, most often first.
+    pub fn hottest(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut addresses: Vec<_> = self.by_address.iter().map(|(&a, &c)| (a, c)).collect();
+        addresses.sort_by_key(|&(address, count)| (Reverse(count), address));
+        addresses.truncate(n);
+        addresses
+    }
+}
+
+impl Tracer for Profiler {
+    fn instruction(&mut self, ip: usize, op_code: OpCode, _params: &[Atom]) {
+        *self.by_address.entry(ip).or_default() += 1;
+        *self.by_mnemonic.entry(op_code.mnemonic()).or_default() += 1;
+    }
+}
+
+/// An instruction that ran and the memory it accessed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub ip: usize,
+    pub op_code: OpCode,
+    pub params: Vec<Atom>,
+    pub reads: Vec<(usize, Atom)>,
+    pub writes: Vec<(usize, Atom)>,
+}
+
+/// Keeps the last `capacity` steps.
+#[derive(Clone, Debug)]
+pub struct History {
+    capacity: usize,
+    steps: VecDeque<Step>,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            steps: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// The kept steps, oldest first.
+    pub fn steps(&self) -> impl Iterator<Item = &Step> {
+        self.steps.iter()
+    }
+}
+
+impl Tracer for History {
+    fn instruction(&mut self, ip: usize, op_code: OpCode, params: &[Atom]) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.steps.len() == self.capacity {
+            self.steps.pop_front();
+        }
+        self.steps.push_back(Step {
+            ip,
+            op_code,
+            params: params.to_vec(),
+            reads: Vec::new(),
+            writes: Vec::new(),
+        });
+    }
+    fn read(&mut self, address: usize, value: Atom) {
+        if let Some(step) = self.steps.back_mut() {
+            step.reads.push((address, value));
+        }
+    }
+    fn write(&mut self, address: usize, value: Atom) {
+        if let Some(step) = self.steps.back_mut() {
+            step.writes.push((address, value));
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Machine<T = ()> {
     pub memory: Intcode,
     ip: usize,
     pub halt: bool,
     input: VecDeque<Atom>,
     output: VecDeque<Atom>,
     relative_base: Atom,
-    empty: Atom,
+    fault: Option<Fault>,
+    tracer: T,
+}
+
+/// What happened in a [`Machine::step`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    /// an instruction ran and the machine can go on
+    Running,
+    /// the next instruction reads input but there is none, nothing happened
+    NeedsInput,
+    /// an instruction wrote the value to the output
+    Output(Atom),
+    /// the machine stopped, stepping it again does nothing
+    Halted,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FaultKind {
+    /// the word isn't an instruction
+    InvalidInstruction(Atom),
+    /// a parameter points before the start of memory
+    NegativeAddress(Atom),
+    /// a jump goes before the start of memory
+    NegativeJump(Atom),
 }
 
-impl Iterator for Machine {
+/// An instruction that couldn't run, the machine is left as it was before.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Fault {
+    pub kind: FaultKind,
+    pub ip: usize,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            FaultKind::InvalidInstruction(word) => write!(f, "invalid instruction {}", word)?,
+            FaultKind::NegativeAddress(address) => write!(f, "negative address {}", address)?,
+            FaultKind::NegativeJump(target) => write!(f, "jump to negative address {}", target)?,
+        }
+        write!(f, " at {}", self.ip)
+    }
+}
+
+impl std::error::Error for Fault {}
+
+/// The outputs until the machine halts, needs input or faults, use
+/// [`Machine::step`], [`Machine::check`] or [`Machine::finish`] to tell those
+/// apart.
+impl<T: Tracer> Iterator for Machine<T> {
     type Item = Atom;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while !self.halt && self.output.is_empty() {
-            self.step();
+        loop {
+            if let Some(output) = self.output.pop_front() {
+                return Some(output);
+            }
+            match self.step() {
+                Ok(State::Running | State::Output(_)) => {}
+                Ok(State::NeedsInput | State::Halted) | Err(_) => return None,
+            }
         }
-        self.output.pop_front()
     }
 }
 
-impl Machine {
-    pub fn step(&mut self) {
-        let op_code = self.op_code(self.ip);
+impl<T: Tracer> Machine<T> {
+    /// Runs the next instruction. Outputs are also kept for
+    /// [`output`](Self::output) and the iterator.
+    pub fn step(&mut self) -> Result<State, Fault> {
+        let result = self.try_step();
+        self.fault = result.err();
+        result
+    }
+
+    fn try_step(&mut self) -> Result<State, Fault> {
+        if self.halt {
+            return Ok(State::Halted);
+        }
         let ip = self.ip;
-        self.ip += op_code.len();
+        let op_code = self.op_code(ip).map_err(|kind| Fault { kind, ip })?;
+        if matches!(op_code, OpCode::Input(_)) && self.input.is_empty() {
+            return Ok(State::NeedsInput);
+        }
+        let params = (ip + op_code.len()).min(self.memory.len());
+        self.tracer
+            .instruction(ip, op_code, self.memory.get(ip + 1..params).unwrap_or(&[]));
+        self.execute(ip, op_code).map_err(|kind| Fault { kind, ip })
+    }
+
+    /// Runs `op_code`, it only changes the machine once nothing can go wrong
+    /// anymore.
+    fn execute(&mut self, ip: usize, op_code: OpCode) -> Result<State, FaultKind> {
+        let mut next = ip + op_code.len();
+        let mut state = State::Running;
         use OpCode::*;
         match op_code {
             Add(in1, in2, out) => {
-                #[cfg(test)]
-                {
-                    println!(
-                        "{:5}: add {:?}({}) {:?}({}) {:?}({})",
-                        ip,
-                        in1,
-                        self[(in1, ip + 1)],
-                        in2,
-                        self[(in2, ip + 2)],
-                        out,
-                        self[(out, ip + 3)]
-                    );
-                }
-                self[(out, ip + 3)] = self[(in1, ip + 1)] + self[(in2, ip + 2)];
+                let value = self.read(in1, ip + 1)? + self.read(in2, ip + 2)?;
+                self.write(out, ip + 3, value)?;
             }
             Mul(in1, in2, out) => {
-                #[cfg(test)]
-                {
-                    println!(
-                        "{:5}: mul {:?}({}) {:?}({}) {:?}({})",
-                        ip,
-                        in1,
-                        self[(in1, ip + 1)],
-                        in2,
-                        self[(in2, ip + 2)],
-                        out,
-                        self[(out, ip + 3)]
-                    );
-                }
-                self[(out, ip + 3)] = self[(in1, ip + 1)] * self[(in2, ip + 2)];
+                let value = self.read(in1, ip + 1)? * self.read(in2, ip + 2)?;
+                self.write(out, ip + 3, value)?;
             }
             Halt => {
-                #[cfg(test)]
-                {
-                    println!("{:5}: hcf", ip);
-                }
                 self.halt = true;
+                state = State::Halted;
             }
             Input(dest) => {
-                #[cfg(test)]
-                {
-                    println!("{:5}: in {:?}({})", ip, dest, self[(dest, ip + 1)]);
-                }
-                if let Some(val) = self.input.pop_front() {
-                    self[(dest, ip + 1)] = val
-                }
+                let value = self.input[0];
+                self.write(dest, ip + 1, value)?;
+                self.input.pop_front();
             }
             Output(source) => {
-                #[cfg(test)]
-                {
-                    println!("{:5}: out {:?}({})", ip, source, self.memory[ip + 1]);
-                }
-                self.output.push_back(self[(source, ip + 1)])
+                let value = self.read(source, ip + 1)?;
+                self.output.push_back(value);
+                state = State::Output(value);
             }
             JumpIfTrue(b, t) => {
-                #[cfg(test)]
-                {
-                    println!(
-                        "{:5}: jt {:?}({}) {:?}({})",
-                        ip,
-                        b,
-                        self[(b, ip + 1)],
-                        t,
-                        self[(t, ip + 2)]
-                    );
-                }
-                if self[(b, ip + 1)] != 0 {
-                    self.ip = self[(t, ip + 2)] as usize;
+                if self.read(b, ip + 1)? != 0 {
+                    next = self.target(t, ip + 2)?;
                 }
             }
             JumpIfFalse(b, t) => {
-                #[cfg(test)]
-                {
-                    println!(
-                        "{:5}: jf {:?}({}) {:?}({})",
-                        ip,
-                        b,
-                        self[(b, ip + 1)],
-                        t,
-                        self[(t, ip + 2)]
-                    );
-                }
-                if self[(b, ip + 1)] == 0 {
-                    self.ip = self[(t, ip + 2)] as usize;
+                if self.read(b, ip + 1)? == 0 {
+                    next = self.target(t, ip + 2)?;
                 }
             }
             LessThan(in1, in2, out) => {
-                #[cfg(test)]
-                {
-                    println!(
-                        "{:5}: lt {:?}({}) {:?}({}) {:?}({})",
-                        ip,
-                        in1,
-                        self[(in1, ip + 1)],
-                        in2,
-                        self[(in2, ip + 2)],
-                        out,
-                        self[(out, ip + 3)]
-                    );
-                }
-                self[(out, ip + 3)] = if self[(in1, ip + 1)] < self[(in2, ip + 2)] {
-                    1
-                } else {
-                    0
-                }
+                let value = self.read(in1, ip + 1)? < self.read(in2, ip + 2)?;
+                self.write(out, ip + 3, value.into())?;
             }
             Equals(in1, in2, out) => {
-                #[cfg(test)]
-                {
-                    println!(
-                        "{:5}: eq {:?}({}) {:?}({}) {:?}({})",
-                        ip,
-                        in1,
-                        self[(in1, ip + 1)],
-                        in2,
-                        self[(in2, ip + 2)],
-                        out,
-                        self[(out, ip + 3)]
-                    );
-                }
-                self[(out, ip + 3)] = if self[(in1, ip + 1)] == self[(in2, ip + 2)] {
-                    1
-                } else {
-                    0
-                }
+                let value = self.read(in1, ip + 1)? == self.read(in2, ip + 2)?;
+                self.write(out, ip + 3, value.into())?;
             }
             AdjustRelativeBase(adj) => {
-                #[cfg(test)]
-                {
-                    println!("{:5}: adj {:?}({})", ip, adj, self.memory[ip + 1]);
-                }
-                self.relative_base += self[(adj, ip + 1)];
+                self.relative_base += self.read(adj, ip + 1)?;
             }
         };
+        self.ip = next;
+        Ok(state)
+    }
+
+    /// The value of the parameter at `idx`, reads of memory are traced.
+    fn read(&mut self, mode: PMode, idx: usize) -> Result<Atom, FaultKind> {
+        let address = self.idx(mode, idx)?;
+        let value = self.memory.get(address).copied().unwrap_or(0);
+        if mode != PMode::Immediate {
+            self.tracer.read(address, value);
+        }
+        Ok(value)
+    }
+
+    /// The address a jump with its target at `idx` goes to.
+    fn target(&mut self, mode: PMode, idx: usize) -> Result<usize, FaultKind> {
+        let target = self.read(mode, idx)?;
+        usize::try_from(target).map_err(|_| FaultKind::NegativeJump(target))
+    }
+
+    fn write(&mut self, mode: PMode, idx: usize, value: Atom) -> Result<(), FaultKind> {
+        let address = self.idx(mode, idx)?;
+        self.reserve(address);
+        self.memory[address] = value;
+        self.tracer.write(address, value);
+        Ok(())
+    }
+
+    /// Whether the machine waits for input, stepping it would give
+    /// [`State::NeedsInput`].
+    pub fn needs_input(&self) -> bool {
+        !self.halt && self.input.is_empty() && matches!(self.op_code(self.ip), Ok(OpCode::Input(_)))
+    }
+
+    /// The fault of the last step, e.g. the one that ended the iterator.
+    pub fn fault(&self) -> Option<Fault> {
+        self.fault
+    }
+
+    /// Fails with the fault of the last step if there is one.
+    pub fn check(&self) -> Result<(), Fault> {
+        match self.fault {
+            Some(fault) => Err(fault),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs the machine to its end, it fails if the program faults or waits
+    /// for input instead of halting. Outputs are kept like in [`run`](Self::run).
+    pub fn finish(&mut self) -> Result<()> {
+        match self.run()? {
+            State::Halted => Ok(()),
+            _ => Err(anyhow!("the program waits for input at {}", self.ip)),
+        }
     }
 
-    pub fn run(&mut self) {
-        while !self.halt {
-            self.step();
+    /// The machine with `tracer` instead of the current tracer.
+    pub fn with_tracer<U: Tracer>(self, tracer: U) -> Machine<U> {
+        Machine {
+            memory: self.memory,
+            ip: self.ip,
+            halt: self.halt,
+            input: self.input,
+            output: self.output,
+            relative_base: self.relative_base,
+            fault: self.fault,
+            tracer,
+        }
+    }
+
+    pub fn tracer(&self) -> &T {
+        &self.tracer
+    }
+
+    pub fn tracer_mut(&mut self) -> &mut T {
+        &mut self.tracer
+    }
+
+    pub fn into_tracer(self) -> T {
+        self.tracer
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn relative_base(&self) -> Atom {
+        self.relative_base
+    }
+
+    /// The inputs that weren't read yet.
+    pub fn pending_input(&self) -> &VecDeque<Atom> {
+        &self.input
+    }
+
+    /// The instruction that runs next, `None` if it doesn't decode.
+    pub fn next_instruction(&self) -> Option<Line> {
+        decode_at(&self.memory, self.ip)
+    }
+
+    /// The state of the machine as JSON, the tracer is not part of it.
+    pub fn snapshot(&self) -> String {
+        serde_json::json!({
+            "memory": &self.memory[..],
+            "ip": self.ip,
+            "relative_base": self.relative_base,
+            "halt": self.halt,
+            "input": self.input,
+            "output": self.output,
+        })
+        .to_string()
+    }
+
+    /// Goes back to the state in a [`snapshot`](Self::snapshot).
+    pub fn restore(&mut self, snapshot: &str) -> Result<()> {
+        let value: serde_json::Value = serde_json::from_str(snapshot)?;
+        let field = |name: &str| {
+            value
+                .get(name)
+                .ok_or_else(|| anyhow!("the snapshot has no {}", name))
+        };
+        let atoms = |name: &str| -> Result<Vec<Atom>> {
+            field(name)?
+                .as_array()
+                .ok_or_else(|| anyhow!("{} is not an array", name))?
+                .iter()
+                .map(|atom| {
+                    atom.as_i64()
+                        .ok_or_else(|| anyhow!("{} is not a number", atom))
+                })
+                .collect()
+        };
+        let memory = atoms("memory")?;
+        let input = atoms("input")?;
+        let output = atoms("output")?;
+        let ip = field("ip")?.as_u64().ok_or_else(|| anyhow!("invalid ip"))?;
+        let relative_base = field("relative_base")?
+            .as_i64()
+            .ok_or_else(|| anyhow!("invalid relative_base"))?;
+        let halt = field("halt")?
+            .as_bool()
+            .ok_or_else(|| anyhow!("invalid halt"))?;
+        self.memory = memory.into();
+        self.input = input.into();
+        self.output = output.into();
+        self.ip = ip.try_into()?;
+        self.relative_base = relative_base;
+        self.halt = halt;
+        self.fault = None;
+        Ok(())
+    }
+
+    /// Runs the machine until it halts or needs input.
+    pub fn run(&mut self) -> Result<State, Fault> {
+        loop {
+            match self.step()? {
+                State::Running | State::Output(_) => {}
+                state => return Ok(state),
+            }
         }
     }
 
@@ -281,42 +962,11 @@ impl Machine {
         it.for_each(|v| self.input.push_back(v));
     }
 
-    fn op_code(&self, ip: usize) -> OpCode {
-        if ip >= self.memory.len() {
-            return OpCode::Halt;
-        }
-        let op_code = self.memory[ip] % 100;
-        let param_mode1 = (self.memory[ip] / 100 % 10) as u8;
-        let param_mode2 = (self.memory[ip] / 1000 % 10) as u8;
-        let param_mode3 = (self.memory[ip] / 10000 % 10) as u8;
-        match op_code {
-            1 => OpCode::Add(
-                PMode::from(param_mode1),
-                PMode::from(param_mode2),
-                PMode::from(param_mode3),
-            ),
-            2 => OpCode::Mul(
-                PMode::from(param_mode1),
-                PMode::from(param_mode2),
-                PMode::from(param_mode3),
-            ),
-            3 => OpCode::Input(PMode::from(param_mode1)),
-            4 => OpCode::Output(PMode::from(param_mode1)),
-            5 => OpCode::JumpIfTrue(PMode::from(param_mode1), PMode::from(param_mode2)),
-            6 => OpCode::JumpIfFalse(PMode::from(param_mode1), PMode::from(param_mode2)),
-            7 => OpCode::LessThan(
-                PMode::from(param_mode1),
-                PMode::from(param_mode2),
-                PMode::from(param_mode3),
-            ),
-            8 => OpCode::Equals(
-                PMode::from(param_mode1),
-                PMode::from(param_mode2),
-                PMode::from(param_mode3),
-            ),
-            9 => OpCode::AdjustRelativeBase(PMode::from(param_mode1)),
-            99 => OpCode::Halt,
-            _ => unimplemented!(),
+    /// The instruction at `ip`, running past the end of memory halts.
+    fn op_code(&self, ip: usize) -> Result<OpCode, FaultKind> {
+        match self.memory.get(ip) {
+            Some(&word) => OpCode::decode(word).ok_or(FaultKind::InvalidInstruction(word)),
+            None => Ok(OpCode::Halt),
         }
     }
 
@@ -329,56 +979,24 @@ impl Machine {
             }
         }
     }
-    fn idx(&self, mode: PMode, idx: usize) -> usize {
-        let idx = match mode {
-            PMode::Immediate => idx,
-            PMode::Positional => {
-                let val = self.memory[idx];
-                if val < 0 {
-                    panic!("negative number for positional index")
-                }
-                val as usize
-            }
-            PMode::Relative => {
-                let val = self.memory[idx] + self.relative_base;
-                if val < 0 {
-                    panic!("negative number for relative index")
-                }
-                val as usize
-            }
+    /// The address of the parameter at `idx`.
+    fn idx(&self, mode: PMode, idx: usize) -> Result<usize, FaultKind> {
+        let address = match mode {
+            PMode::Immediate => return Ok(idx),
+            PMode::Positional => self.memory.get(idx).copied().unwrap_or(0),
+            PMode::Relative => self.memory.get(idx).copied().unwrap_or(0) + self.relative_base,
         };
-
-        idx
-    }
-}
-
-impl Index<(PMode, usize)> for Machine {
-    type Output = i64;
-    fn index(&self, (mode, idx): (PMode, usize)) -> &i64 {
-        let idx = self.idx(mode, idx);
-        if idx >= self.memory.len() {
-            &self.empty
-        } else {
-            &self.memory[idx]
-        }
-    }
-}
-
-impl IndexMut<(PMode, usize)> for Machine {
-    fn index_mut(&mut self, (mode, idx): (PMode, usize)) -> &mut i64 {
-        let idx = self.idx(mode, idx);
-        self.reserve(idx);
-        &mut self[idx]
+        usize::try_from(address).map_err(|_| FaultKind::NegativeAddress(address))
     }
 }
 
-impl Index<usize> for Machine {
+impl<T> Index<usize> for Machine<T> {
     type Output = i64;
     fn index(&self, idx: usize) -> &i64 {
         &self.memory[idx]
     }
 }
-impl IndexMut<usize> for Machine {
+impl<T> IndexMut<usize> for Machine<T> {
     fn index_mut(&mut self, idx: usize) -> &mut i64 {
         &mut self.memory[idx]
     }
@@ -393,7 +1011,8 @@ impl From<Intcode> for Machine {
             input: VecDeque::new(),
             output: VecDeque::new(),
             relative_base: 0,
-            empty: 0,
+            fault: None,
+            tracer: (),
         }
     }
 }
@@ -413,7 +1032,8 @@ impl From<Vec<Atom>> for Machine {
             input: VecDeque::new(),
             output: VecDeque::new(),
             relative_base: 0,
-            empty: 0,
+            fault: None,
+            tracer: (),
         }
     }
 }
@@ -441,7 +1061,7 @@ mod tests {
     fn parameter_mode_test() {
         let memory = [1002, 4, 3, 4, 33];
         let mut machine = Machine::from(&memory[..]);
-        machine.run();
+        machine.run().unwrap();
         assert_eq!(machine.memory[..], vec![1002, 4, 3, 4, 99]);
     }
 
@@ -509,11 +1129,11 @@ mod tests {
         assert_eq!(machine.next(), Some(0));
         let mut machine = Machine::from(&memory[..]);
         machine.input(1);
-        machine.run();
+        machine.run().unwrap();
         assert_eq!(machine.next(), Some(1));
         let mut machine = Machine::from(&memory[..]);
         machine.input(-1);
-        machine.run();
+        machine.run().unwrap();
         assert_eq!(machine.next(), Some(1));
     }
 
@@ -572,4 +1192,176 @@ mod tests {
         let mut machine = Machine::from(&memory[..]);
         assert_eq!(machine.next(), Some(memory[1]));
     }
+
+    #[test]
+    fn disassemble_test() {
+        let lines = disassemble(&[1002, 4, 3, 4, 33]);
+        assert_eq!(
+            lines,
+            [
+                Line::Instruction {
+                    address: 0,
+                    op_code: OpCode::Mul(PMode::Positional, PMode::Immediate, PMode::Positional),
+                    params: vec![4, 3, 4],
+                },
+                Line::Data {
+                    address: 4,
+                    words: vec![33],
+                },
+            ]
+        );
+        assert_eq!(lines[0].to_string(), "mul 4, #3, 4");
+
+        let text: Vec<_> = disassemble(&[109, 1, 204, -1, -5, 30099, 7, 1, 2])
+            .iter()
+            .map(Line::to_string)
+            .collect();
+        assert_eq!(text, ["adj #1", "out [-1]", "data -5, 30099, 7, 1, 2"]);
+
+        assert_eq!(
+            listing(&[1105, 1, 4, 123456, 99]),
+            concat!(
+                " 0: 1105 1 4                 jt #1, #4\n",
+                " 3: 123456                   data 123456\n",
+                ">4: 99                       hcf\n",
+            )
+        );
+    }
+
+    #[test]
+    fn assemble_test() {
+        let program = assemble(
+            "
+            loop: in x         ; read a number
+                  jf x, #end
+                  out x
+                  jt #1, #loop
+            end:  hcf
+            x:    data 0
+            ",
+        )
+        .unwrap();
+        assert_eq!(program[..], [3, 11, 1006, 11, 10, 4, 11, 1105, 1, 0, 99, 0]);
+        let mut machine = Machine::from(program);
+        machine.input_iter([5, 7, 0]);
+        assert_eq!(machine.collect::<Vec<_>>(), [5, 7]);
+
+        let text = concat!(
+            "adj #1\n",
+            "out [-1]\n",
+            "add 100, #1, 100\n",
+            "eq 100, #16, 101\n",
+            "jf 101, #0\n",
+            "hcf\n",
+        );
+        let quine = assemble(text).unwrap();
+        assert_eq!(
+            quine[..],
+            [109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99]
+        );
+        assert_eq!(source(&quine), text);
+        let memory = [
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        assert_eq!(assemble(&source(&memory)).unwrap()[..], memory);
+
+        assert!(assemble("jt #1, #nowhere").is_err());
+        assert!(assemble("a: hcf\na: hcf").is_err());
+        assert!(assemble("nop").is_err());
+        assert!(assemble("add 1, 2").is_err());
+        assert!(assemble("data").is_err());
+    }
+
+    #[test]
+    fn tracer_test() {
+        let program = assemble("in 9\nmul 9, #3, 10\nout 10\nhcf\ndata 0, 0").unwrap();
+        let mut machine =
+            Machine::from(&program).with_tracer((Profiler::default(), History::new(2)));
+        machine.input(7);
+        machine.run().unwrap();
+        assert_eq!(machine.output(), [21]);
+        let (profiler, history) = machine.tracer();
+        assert_eq!(profiler.total(), 4);
+        assert_eq!(profiler.by_mnemonic["mul"], 1);
+        assert_eq!(profiler.hottest(1), [(0, 1)]);
+        let steps: Vec<_> = history.steps().collect();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].ip, 6);
+        assert_eq!(steps[0].reads, [(10, 21)]);
+        assert_eq!(steps[1].op_code, OpCode::Halt);
+
+        let mut machine = Machine::from(program).with_tracer(Logger::new(Vec::new()));
+        machine.input(7);
+        machine.run().unwrap();
+        assert_eq!(
+            String::from_utf8(machine.into_tracer().into_inner()).unwrap(),
+            concat!(
+                "    0: in 9\n",
+                "       write 9 = 7\n",
+                "    2: mul 9, #3, 10\n",
+                "       read 9 = 7\n",
+                "       write 10 = 21\n",
+                "    6: out 10\n",
+                "       read 10 = 21\n",
+                "    8: hcf\n",
+            )
+        );
+    }
+
+    #[test]
+    fn needs_input_test() {
+        let mut machine = Machine::from(&[3, 9, 4, 9, 3, 9, 4, 9, 99, 0][..]);
+        machine.input(5);
+        assert_eq!(machine.step(), Ok(State::Running));
+        assert_eq!(machine.step(), Ok(State::Output(5)));
+        assert!(machine.needs_input());
+        assert_eq!(machine.run(), Ok(State::NeedsInput));
+        assert_eq!(machine.ip(), 4);
+        assert_eq!(
+            machine.finish().unwrap_err().to_string(),
+            "the program waits for input at 4"
+        );
+        assert_eq!(machine.next(), Some(5));
+        assert_eq!(machine.next(), None);
+        machine.input(6);
+        assert_eq!(machine.next(), Some(6));
+        assert_eq!(machine.run(), Ok(State::Halted));
+        assert_eq!(machine.step(), Ok(State::Halted));
+        machine.finish().unwrap();
+    }
+
+    #[test]
+    fn fault_test() {
+        let program = [1101, 3, 4, 9, 1, -1, 0, 0, 99, 0];
+        let mut machine = Machine::from(&program[..]);
+        let fault = Fault {
+            kind: FaultKind::NegativeAddress(-1),
+            ip: 4,
+        };
+        assert_eq!(machine.run(), Err(fault));
+        assert_eq!(machine.ip(), 4);
+        assert_eq!(machine.memory[..], [1101, 3, 4, 9, 1, -1, 0, 0, 99, 7]);
+        assert_eq!(fault.to_string(), "negative address -1 at 4");
+        assert_eq!(machine.next(), None);
+        assert_eq!(machine.check(), Err(fault));
+
+        let mut machine = Machine::from(&[1105, 1, -3][..]);
+        assert_eq!(
+            machine.step().unwrap_err().kind,
+            FaultKind::NegativeJump(-3)
+        );
+        assert_eq!(machine.ip(), 0);
+
+        let mut machine = Machine::from(&[1101, 20, 22, 4, 0][..]);
+        assert_eq!(machine.step(), Ok(State::Running));
+        assert_eq!(
+            machine.step(),
+            Err(Fault {
+                kind: FaultKind::InvalidInstruction(42),
+                ip: 4
+            })
+        );
+    }
 }
@@ -1,3 +0,0 @@
-include!("./day_mods.rs");
-
-pub mod intcode;
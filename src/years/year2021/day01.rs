@@ -12,17 +12,20 @@ impl Day {
 }
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let input: Vec<_> = input
-            .split('\n')
-            .filter_map(|n| n.parse::<i32>().ok())
-            .collect();
-        Box::new(Day::new(input))
+            .lines()
+            .map(|n| {
+                n.parse::<i32>()
+                    .map_err(|e| anyhow::anyhow!("invalid depth {:?}: {}", n, e))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new(input)))
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut acc = 0;
         let mut prev = None;
         self.input.iter().for_each(|n| {
@@ -33,10 +36,10 @@ impl crate::Day for Day {
             }
             prev = Some(n);
         });
-        format!("{}", acc)
+        Ok(acc.into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let input = self.input.iter();
         let mut acc = 0;
         let mut window = std::collections::VecDeque::new();
@@ -55,6 +58,6 @@ impl crate::Day for Day {
                 }
             }
         });
-        format!("{}", acc)
+        Ok(acc.into())
     }
 }
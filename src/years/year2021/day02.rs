@@ -10,20 +10,23 @@ enum Direction {
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let input: Vec<_> = input
-            .split('\n')
-            .filter_map(|n| {
-                let n = n.split(' ').collect::<Vec<_>>();
-                Some(match n[0].chars().next()? {
-                    'u' => Direction::Up(n[1].parse::<i64>().ok()?),
-                    'd' => Direction::Down(n[1].parse::<i64>().ok()?),
-                    'f' => Direction::Forward(n[1].parse::<i64>().ok()?),
-                    _ => None?,
+            .lines()
+            .map(|line| {
+                let (direction, n) = line
+                    .split_once(' ')
+                    .ok_or_else(|| anyhow::anyhow!("invalid command {:?}", line))?;
+                let n = n.parse::<i64>()?;
+                Ok(match direction {
+                    "up" => Direction::Up(n),
+                    "down" => Direction::Down(n),
+                    "forward" => Direction::Forward(n),
+                    _ => return Err(anyhow::anyhow!("invalid direction {:?}", direction)),
                 })
             })
-            .collect();
-        Box::new(Day::new(input))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -38,7 +41,7 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut horizontal: i64 = 0;
         let mut vertical: i64 = 0;
         self.input.iter().for_each(|dir| match dir {
@@ -46,10 +49,10 @@ impl crate::Day for Day {
             Direction::Down(n) => vertical += n,
             Direction::Forward(n) => horizontal += n,
         });
-        format!("{} x {} = {}", horizontal, vertical, horizontal * vertical)
+        Ok((horizontal * vertical).into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut horizontal = 0;
         let mut vertical = 0;
         let mut aim = 0;
@@ -61,6 +64,6 @@ impl crate::Day for Day {
                 vertical += n * aim;
             }
         });
-        format!("{} x {} = {}", horizontal, vertical, horizontal * vertical)
+        Ok((horizontal * vertical).into())
     }
 }
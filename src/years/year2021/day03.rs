@@ -46,26 +46,24 @@ impl FromIterator<bool> for BitNumber {
 
 pub struct DayGen;
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let input = input
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        let input: Input = input
             .split('\n')
-            .filter_map(|n| {
-                if !n.is_empty() {
-                    Some(
-                        n.chars()
-                            .map(|c| match c {
-                                '1' => true,
-                                '0' => false,
-                                _ => panic!("error in input"),
-                            })
-                            .collect(),
-                    )
-                } else {
-                    None
-                }
+            .filter(|n| !n.is_empty())
+            .map(|n| {
+                n.chars()
+                    .map(|c| match c {
+                        '1' => Ok(true),
+                        '0' => Ok(false),
+                        _ => Err(anyhow::anyhow!("invalid bit {:?} in {:?}", c, n)),
+                    })
+                    .collect::<anyhow::Result<BitNumber>>()
             })
-            .collect();
-        Box::new(Day::new(input))
+            .collect::<anyhow::Result<_>>()?;
+        if input.is_empty() {
+            return Err(anyhow::anyhow!("no diagnostic report"));
+        }
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -107,7 +105,7 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let n = self.input[0].len();
         let mut ones = vec![0; n];
         let mut zeros = vec![0; n];
@@ -133,16 +131,11 @@ impl crate::Day for Day {
             }
         }
 
-        format!(
-            "The power consumption is {} = {} x {}",
-            epsilon * gamma,
-            gamma,
-            epsilon
-        )
+        Ok((epsilon * gamma).into())
     }
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let oxygen: u64 = self.calc_o2_co2(false);
         let co2: u64 = self.calc_o2_co2(true);
-        format!("{} = {} x {}", oxygen * co2, oxygen, co2)
+        Ok((oxygen * co2).into())
     }
 }
@@ -1,15 +1,20 @@
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let (numbers, boards) = input.split_once("\n\n").unwrap();
-        let numbers = numbers.split(',').map(|n| n.parse().unwrap()).collect();
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        let (numbers, boards) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow::anyhow!("expected numbers and boards"))?;
+        let numbers = numbers
+            .split(',')
+            .map(|n| n.parse())
+            .collect::<Result<_, _>>()?;
         let boards = boards
             .split("\n\n")
             .filter(|s| !s.is_empty())
             .map(Board::new)
-            .collect();
-        Box::new(Day::new((numbers, boards)))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new((numbers, boards))))
     }
 }
 
@@ -20,17 +25,19 @@ struct Board {
 }
 
 impl Board {
-    fn new(s: &str) -> Self {
+    fn new(s: &str) -> anyhow::Result<Self> {
         let numbers: Vec<_> = s
             .split('\n')
             .flat_map(|l| l.split(' '))
             .filter(|n| !n.is_empty())
-            .map(|n| n.parse().unwrap())
-            .collect();
-        Board {
-            numbers: numbers.try_into().unwrap(),
+            .map(|n| n.parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Board {
+            numbers: numbers
+                .try_into()
+                .map_err(|n: Vec<_>| anyhow::anyhow!("board has {} numbers instead of 25", n.len()))?,
             marked: [false; 25],
-        }
+        })
     }
     fn mark(&mut self, called: u64) -> Option<u64> {
         self.numbers
@@ -73,7 +80,7 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let (numbers, mut boards) = (&self.input.0, self.input.1.clone());
         let mut win = None;
         let mut win_num = 0;
@@ -89,12 +96,12 @@ impl crate::Day for Day {
             }
         }
         match win {
-            Some(val) => format!("Won with {} points", val * win_num),
-            None => String::from("We didn't win ?!?"),
+            Some(val) => Ok((val * win_num).into()),
+            None => Err(anyhow::anyhow!("no board wins")),
         }
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let (numbers, boards) = (&self.input.0, self.input.1.clone());
         let (boards, num) = numbers
             .iter()
@@ -120,10 +127,9 @@ impl crate::Day for Day {
                     )
                 }
             });
-        if let Some(val) = boards[0].win() {
-            format!("Last winner has {} points", val * num)
-        } else {
-            String::from("No last winner")
+        match boards.first().and_then(Board::win) {
+            Some(val) => Ok((val * num).into()),
+            None => Err(anyhow::anyhow!("the last board never wins")),
         }
     }
 }
@@ -6,12 +6,12 @@ use anyhow::anyhow;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let input = input
-            .split('\n')
-            .filter_map(|line| line.parse().ok())
-            .collect();
-        Box::new(Day::new(input))
+            .lines()
+            .map(|line| line.parse())
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -61,7 +61,7 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut field: HashMap<(i64, i64), i64> = HashMap::new();
         self.input
             .iter()
@@ -78,10 +78,10 @@ impl crate::Day for Day {
                 }
             });
 
-        field.into_values().filter(|v| *v > 1).count().to_string()
+        Ok(field.into_values().filter(|v| *v > 1).count().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut field: HashMap<(i64, i64), i64> = HashMap::new();
         self.input.iter().for_each(|l| {
             let dx = (l.b.0 - l.a.0).signum();
@@ -95,6 +95,6 @@ impl crate::Day for Day {
             }
         });
 
-        field.into_values().filter(|v| *v > 1).count().to_string()
+        Ok(field.into_values().filter(|v| *v > 1).count().into())
     }
 }
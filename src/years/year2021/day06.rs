@@ -2,12 +2,12 @@ use std::collections::HashMap;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let mut map = HashMap::new();
-        input.trim().split(',').for_each(|line| {
-            *map.entry(line.parse().unwrap()).or_insert(0) += 1;
-        });
-        Box::new(Day::new(map))
+        for timer in input.trim().split(',') {
+            *map.entry(timer.parse()?).or_insert(0) += 1;
+        }
+        Ok(Box::new(Day::new(map)))
     }
 }
 
@@ -24,8 +24,8 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        (0..80)
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok((0..80)
             .fold(self.input.to_owned(), |map, _| {
                 let mut map: Input = map.into_iter().map(|(k, v)| (k - 1, v)).collect();
                 if let Some(n) = map.remove(&-1) {
@@ -36,11 +36,11 @@ impl crate::Day for Day {
             })
             .values()
             .sum::<usize>()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
-        (0..256)
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok((0..256)
             .fold(self.input.to_owned(), |map, _| {
                 let mut map: Input = map.into_iter().map(|(k, v)| (k - 1, v)).collect();
                 if let Some(n) = map.remove(&-1) {
@@ -51,6 +51,6 @@ impl crate::Day for Day {
             })
             .values()
             .sum::<usize>()
-            .to_string()
+            .into())
     }
 }
@@ -1,13 +1,16 @@
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let input = input
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        let input: Input = input
             .trim()
             .split(',')
-            .filter_map(|line| line.parse().ok())
-            .collect();
-        Box::new(Day::new(input))
+            .map(|line| line.parse())
+            .collect::<Result<_, _>>()?;
+        if input.is_empty() {
+            return Err(anyhow::anyhow!("no crab positions"));
+        }
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -24,17 +27,17 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut sorted = self.input.clone();
         sorted.sort_unstable();
         let median = sorted[sorted.len() / 2];
         let fuel: i64 = self.input.iter().map(|p| (p - median).abs()).sum();
-        fuel.to_string()
+        Ok(fuel.into())
     }
 
-    fn part2(&self) -> String {
-        let min = *self.input.iter().min().unwrap();
-        let max = *self.input.iter().max().unwrap();
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        let min = self.input.iter().copied().min().unwrap_or_default();
+        let max = self.input.iter().copied().max().unwrap_or_default();
         let fuel: i64 = (min..max)
             .map(|i| {
                 self.input
@@ -44,7 +47,7 @@ impl crate::Day for Day {
                     .sum()
             })
             .min()
-            .unwrap();
-        fuel.to_string()
+            .unwrap_or_default();
+        Ok(fuel.into())
     }
 }
@@ -7,13 +7,12 @@ use std::{
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let input = input
             .lines()
-            .map(|l| l.parse().unwrap())
-            // .filter_map(|line| line.parse().ok())
-            .collect();
-        Box::new(Day::new(input))
+            .map(|l| l.parse())
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -188,20 +187,21 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.input
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.input
             .iter()
             .flat_map(Digits::outputs)
             .filter(|o| [2, 4, 3, 7].contains(&o.len()))
             .count()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
-        self.input
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self
+            .input
             .iter()
             .map(Digits::value)
             .sum::<u64>()
-            .to_string()
+            .into())
     }
 }
@@ -2,17 +2,19 @@ use std::collections::{BTreeMap, HashSet};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
         let input = input
             .lines()
             .enumerate()
             .flat_map(|(y, l)| {
-                l.chars()
-                    .enumerate()
-                    .map(move |(x, c)| ((x, y), c as i64 - '0' as i64))
+                l.chars().enumerate().map(move |(x, c)| {
+                    c.to_digit(10)
+                        .map(|h| ((x, y), h as i64))
+                        .ok_or_else(|| anyhow::anyhow!("invalid height {:?}", c))
+                })
             })
-            .collect();
-        Box::new(Day::new(input))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Box::new(Day::new(input)))
     }
 }
 
@@ -29,8 +31,9 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.input
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self
+            .input
             .iter()
             .filter_map(|((x, y), v)| {
                 let v = *v + 1;
@@ -50,10 +53,10 @@ impl crate::Day for Day {
                 (Some(&v) == min).then(|| v)
             })
             .sum::<i64>()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut basins: Vec<HashSet<(usize, usize)>> =
             self.input.iter().fold(Vec::new(), |mut sets, (c, v)| {
                 if *v != 9 {
@@ -85,7 +88,7 @@ impl crate::Day for Day {
         basins.sort_by_key(HashSet::len);
         let last3: usize = basins.iter().rev().take(3).map(HashSet::len).product();
 
-        format!("{:?}", last3)
+        Ok(last3.into())
     }
 }
 
@@ -1,8 +1,8 @@
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -13,13 +13,16 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
         let input: Vec<Vec<_>> = input
             .lines()
             .map(str::chars)
             .map(Iterator::collect)
             .collect();
-        Self { input }
+        if let Some(c) = input.iter().flatten().find(|c| !"()[]{}<>".contains(**c)) {
+            return Err(anyhow::anyhow!("invalid character {:?}", c));
+        }
+        Ok(Self { input })
     }
 }
 
@@ -42,18 +45,18 @@ fn corrupt(line: &[char]) -> Result<char, Vec<char>> {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.input.iter().map(|c| corrupt(c)).filter_map(Result::ok).map(|c| match c {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.input.iter().map(|c| corrupt(c)).filter_map(Result::ok).map(|c| match c {
             // <{[(
             ')' => 3,
             ']' => 57,
             '}' => 1197,
             '>' => 25137,
             _ => unreachable!(),
-        }).sum::<i64>().to_string()
+        }).sum::<i64>().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut points: Vec<_> = self.input.iter().filter_map(|c| if let Err(c) = corrupt(c) {
             Some(c)
         } else {
@@ -65,8 +68,11 @@ impl crate::Day for Day {
             '<' => 4,
             _ => unreachable!(),
         })).collect();
+        if points.is_empty() {
+            return Err(anyhow::anyhow!("no incomplete lines"));
+        }
         points.sort_unstable();
-        points[(points.len()-1)/2].to_string()
+        Ok(points[(points.len()-1)/2].into())
     }
 }
 
@@ -89,9 +95,9 @@ mod tests {
             "<{([([[(<>()){}]>(<<{{\n",
             "<{([{{}}[<[[[<>{}]]]>[]]",
         );
-        let day = Day::from_str(input);
+        let day = Day::from_str(input).unwrap();
         assert_eq!(day.input.len(), 10);
         assert_eq!(day.input[0].len(), 24);
-        assert_eq!(day.part2(), 288957u64.to_string());
+        assert_eq!(day.part2().unwrap(), crate::Answer::Integer(288957));
     }
 }
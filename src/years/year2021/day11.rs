@@ -14,8 +14,8 @@ static ADJACENT: [(i8, i8); 8] = [
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -26,19 +26,21 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
         let input = input
             .lines()
             .enumerate()
             .flat_map(|(row, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(col, c)| ((row, col), c as u8 - b'0'))
+                line.chars().enumerate().map(move |(col, c)| {
+                    c.to_digit(10)
+                        .map(|e| ((row, col), e as u8))
+                        .ok_or_else(|| anyhow::anyhow!("invalid energy level {:?}", c))
+                })
             })
-            .collect();
-        Self {
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
             input: Oktopi(input),
-        }
+        })
     }
 }
 
@@ -92,25 +94,25 @@ fn grow_all(map: &mut Oktopi) -> usize {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut map = self.input.clone();
         let mut flashes = 0;
         for _ in 0..100 {
             flashes += grow_all(&mut map);
         }
-        flashes.to_string()
+        Ok(flashes.into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut map = self.input.clone();
         let mut days: u32 = 0;
-        loop {
+        Ok(loop {
             days += 1;
             if grow_all(&mut map) >= map.len() {
                 break days;
             }
         }
-        .to_string()
+        .into())
     }
 }
 
@@ -121,13 +123,15 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        let day = Day::from_str(crate::YEARS[&2021][&11].1);
-        assert_eq!(day.part1(), "1705");
+        let input = crate::inputs::input(2021, 11).unwrap();
+        let day = Day::from_str(&input).unwrap();
+        assert_eq!(day.part1().unwrap(), crate::Answer::Integer(1705));
     }
 
     #[test]
     fn part2_test() {
-        let day = Day::from_str(crate::YEARS[&2021][&11].1);
-        assert_eq!(day.part2(), "265");
+        let input = crate::inputs::input(2021, 11).unwrap();
+        let day = Day::from_str(&input).unwrap();
+        assert_eq!(day.part2().unwrap(), crate::Answer::Integer(265));
     }
 }
@@ -3,8 +3,8 @@ use std::collections::HashMap;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input<'a>(&self, input: &'a str) -> Box<dyn crate::Day + 'a> {
-        Box::new(Day::from_str(input))
+    fn input<'a>(&self, input: &'a str) -> anyhow::Result<Box<dyn crate::Day + 'a>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -15,14 +15,32 @@ struct Day<'a> {
 }
 
 impl<'a> Day<'a> {
-    pub fn from_str(input: &'a str) -> Self {
-        let edges: Vec<_> = input.lines().map(|x| x.split_once('-').unwrap()).collect();
+    pub fn from_str(input: &'a str) -> anyhow::Result<Self> {
+        let edges: Vec<_> = input
+            .lines()
+            .map(|x| {
+                x.split_once('-')
+                    .ok_or_else(|| anyhow::anyhow!("invalid edge {:?}", x))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        // paths could go back and forth between two big caves forever
+        let big = |cave: &str| !cave.chars().any(char::is_lowercase);
+        if let Some((a, b)) = edges.iter().find(|(a, b)| big(a) && big(b)) {
+            return Err(anyhow::anyhow!(
+                "big caves {} and {} are connected, there are endless paths",
+                a,
+                b
+            ));
+        }
         let mut input = HashMap::new();
         for (a, b) in edges {
             input.entry(a).or_insert_with(Vec::new).push(b);
             input.entry(b).or_insert_with(Vec::new).push(a);
         }
-        Self { input }
+        if !input.contains_key("start") {
+            return Err(anyhow::anyhow!("cave system has no start"));
+        }
+        Ok(Self { input })
     }
 }
 
@@ -52,7 +70,7 @@ fn visit<'a, F>(
 }
 
 impl<'a> crate::Day for Day<'a> {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut paths = Vec::new();
         let mut partial = Vec::new();
         visit(
@@ -62,10 +80,10 @@ impl<'a> crate::Day for Day<'a> {
             &mut partial,
             "start",
         );
-        paths.len().to_string()
+        Ok(paths.len().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut paths = Vec::new();
         let mut partial = Vec::new();
         visit(
@@ -88,7 +106,7 @@ impl<'a> crate::Day for Day<'a> {
             &mut partial,
             "start",
         );
-        paths.len().to_string()
+        Ok(paths.len().into())
     }
 }
 
@@ -98,120 +116,20 @@ mod tests {
     use crate::Day as _;
 
     #[test]
-    fn part1_1_test() {
-        let input = concat![
-            "start-A\n",
-            "start-b\n",
-            "A-c\n",
-            "A-b\n",
-            "b-d\n",
-            "A-end\n",
-            "b-end",
-        ];
-        let day = Day::from_str(input);
-        assert_eq!("10", day.part1());
-    }
-
-    #[test]
-    fn part1_2_test() {
-        let input = concat![
-            "dc-end\n",
-            "HN-start\n",
-            "start-kj\n",
-            "dc-start\n",
-            "dc-HN\n",
-            "LN-dc\n",
-            "HN-end\n",
-            "kj-sa\n",
-            "kj-HN\n",
-            "kj-dc",
-        ];
-        let day = Day::from_str(input);
-        assert_eq!("19", day.part1());
-    }
-
-    #[test]
-    fn part1_3_test() {
-        let input = concat![
-            "fs-end\n",
-            "he-DX\n",
-            "fs-he\n",
-            "start-DX\n",
-            "pj-DX\n",
-            "end-zg\n",
-            "zg-sl\n",
-            "zg-pj\n",
-            "pj-he\n",
-            "RW-he\n",
-            "fs-DX\n",
-            "pj-RW\n",
-            "zg-RW\n",
-            "start-pj\n",
-            "he-WI\n",
-            "zg-he\n",
-            "pj-fs\n",
-            "start-RW",
-        ];
-        let day = Day::from_str(input);
-        assert_eq!("226", day.part1());
-    }
-
-    #[test]
-    fn part2_1_test() {
-        let input = concat![
-            "start-A\n",
-            "start-b\n",
-            "A-c\n",
-            "A-b\n",
-            "b-d\n",
-            "A-end\n",
-            "b-end",
-        ];
-        let day = Day::from_str(input);
-        assert_eq!("36", day.part2());
-    }
-
-    #[test]
-    fn part2_2_test() {
-        let input = concat![
-            "dc-end\n",
-            "HN-start\n",
-            "start-kj\n",
-            "dc-start\n",
-            "dc-HN\n",
-            "LN-dc\n",
-            "HN-end\n",
-            "kj-sa\n",
-            "kj-HN\n",
-            "kj-dc",
-        ];
-        let day = Day::from_str(input);
-        assert_eq!("103", day.part2());
+    fn samples_test() {
+        let samples = crate::inputs::samples(&crate::inputs::crate_dir(), 2021, 12).unwrap();
+        assert_eq!(samples.len(), 3);
+        for sample in samples {
+            let day = Day::from_str(&sample.text).unwrap();
+            assert_eq!(sample.part1, Some(day.part1().unwrap()), "{}", sample.name);
+            assert_eq!(sample.part2, Some(day.part2().unwrap()), "{}", sample.name);
+        }
     }
 
     #[test]
-    fn part2_3_test() {
-        let input = concat![
-            "fs-end\n",
-            "he-DX\n",
-            "fs-he\n",
-            "start-DX\n",
-            "pj-DX\n",
-            "end-zg\n",
-            "zg-sl\n",
-            "zg-pj\n",
-            "pj-he\n",
-            "RW-he\n",
-            "fs-DX\n",
-            "pj-RW\n",
-            "zg-RW\n",
-            "start-pj\n",
-            "he-WI\n",
-            "zg-he\n",
-            "pj-fs\n",
-            "start-RW",
-        ];
-        let day = Day::from_str(input);
-        assert_eq!("3509", day.part2());
+    fn invalid_test() {
+        assert!(Day::from_str("start-A\nA-end\nb").is_err());
+        assert!(Day::from_str("A-b\nb-end").is_err());
+        assert!(Day::from_str("start-A\nA-BC\nBC-end").is_err());
     }
 }
@@ -3,8 +3,8 @@ use std::collections::HashSet;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -21,38 +21,45 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let (dots, folds) = input.split_once("\n\n").unwrap();
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let (dots, folds) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow::anyhow!("expected dots and folds"))?;
         let dots = dots
             .lines()
-            .filter_map(|d| {
+            .map(|d| {
                 d.split_once(',')
                     .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+                    .ok_or_else(|| anyhow::anyhow!("invalid dot {:?}", d))
             })
-            .collect();
+            .collect::<anyhow::Result<_>>()?;
 
-        let folds = folds
+        let folds: Vec<_> = folds
             .lines()
-            .filter_map(|f| {
-                f["fold along ".len()..]
-                    .split_once('=')
+            .map(|f| {
+                f.strip_prefix("fold along ")
+                    .and_then(|f| f.split_once('='))
                     .and_then(|(xy, v)| {
                         Some(match xy {
                             "y" => Fold::Up(v.parse().ok()?),
                             "x" => Fold::Left(v.parse().ok()?),
-                            _ => unreachable!(),
+                            _ => return None,
                         })
                     })
+                    .ok_or_else(|| anyhow::anyhow!("invalid fold {:?}", f))
             })
-            .collect();
-        Self {
-            input: (dots, folds),
+            .collect::<anyhow::Result<_>>()?;
+        if folds.is_empty() {
+            return Err(anyhow::anyhow!("no folds"));
         }
+        Ok(Self {
+            input: (dots, folds),
+        })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let dots = self.input.0.iter().copied().map(|(x, y)| {
             match self.input.1[0] {
                 Fold::Up(fy) if y >= fy => (x, 2*fy - y),
@@ -60,10 +67,10 @@ impl crate::Day for Day {
                 _ => (x, y),
             }
         }).collect::<HashSet<_>>();
-        dots.len().to_string()
+        Ok(dots.len().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let dots = self.input.1.iter().fold(self.input.0.clone(), |dots, fold| {
             dots.into_iter().map(|(x, y)| {
                 match *fold {
@@ -74,18 +81,10 @@ impl crate::Day for Day {
             }).collect::<HashSet<_>>()
         });
 
-        let mut image = String::from("\n\n");
-        image.reserve(dots.len()*6);
-        for y in 0..6 {
-            for x in 0..40 {
-                if dots.contains(&(x, y)) {
-                    image.push('#');
-                } else {
-                    image.push(' ');
-                }
-            }
-            image.push('\n')
-        }
-        image
+        Ok(crate::Answer::art((0..6).map(|y| {
+            (0..40)
+                .map(|x| if dots.contains(&(x, y)) { '#' } else { ' ' })
+                .collect::<String>()
+        })))
     }
 }
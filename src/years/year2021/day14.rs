@@ -3,8 +3,8 @@ use std::{collections::HashMap, str::FromStr};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -14,27 +14,29 @@ struct Input {
 }
 
 impl FromStr for Input {
-    type Err = String;
+    type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (template, insertions) = s
             .split_once("\n\n")
-            .ok_or_else(|| "Could not find \"\\n\\n\" in input".to_string())?;
-        let rule_err = || "Invalid rule".to_string();
-        let template = template.chars().collect();
+            .ok_or_else(|| anyhow::anyhow!("Could not find \"\\n\\n\" in input"))?;
+        let template: Vec<_> = template.chars().collect();
+        if template.is_empty() {
+            return Err(anyhow::anyhow!("Empty polymer template"));
+        }
         let insertions = insertions
             .lines()
             .map(|l| {
-                let mut iter = l.chars();
-                Ok((
-                    (
-                        iter.next().ok_or_else(rule_err)?,
-                        iter.next().ok_or_else(rule_err)?,
-                    ),
-                    iter.nth(4).ok_or_else(rule_err)?,
-                ))
+                let rule_err = || anyhow::anyhow!("Invalid rule {:?}", l);
+                let (pair, insertion) = l.split_once(" -> ").ok_or_else(rule_err)?;
+                let pair: Vec<_> = pair.chars().collect();
+                let insertion: Vec<_> = insertion.chars().collect();
+                match (&pair[..], &insertion[..]) {
+                    (&[a, b], &[c]) => Ok(((a, b), c)),
+                    _ => Err(rule_err()),
+                }
             })
-            .collect::<Result<HashMap<_, _>, String>>()?;
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
         Ok(Self {
             template,
             insertions,
@@ -47,9 +49,9 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let input: Input = input.parse().expect("parse input");
-        Self { input }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let input: Input = input.parse()?;
+        Ok(Self { input })
     }
 }
 
@@ -86,12 +88,12 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.run(10).to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(10).into())
     }
 
-    fn part2(&self) -> String {
-        self.run(40).to_string()
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(40).into())
     }
 }
 
@@ -104,17 +106,17 @@ mod tests {
 
     #[test]
     fn part1_test() {
-        let input = crate::YEARS[&2021][&14].2;
-        let day = Day::from_str(input);
-        assert_eq!("1588", day.part1());
+        let input = crate::inputs::sample(2021, 14).unwrap();
+        let day = Day::from_str(&input).unwrap();
+        assert_eq!(crate::Answer::Integer(1588), day.part1().unwrap());
     }
 
     #[bench]
     fn part2(b: &mut test::Bencher) {
-        let input = crate::YEARS[&2021][&14].2;
-        let day = Day::from_str(input);
+        let input = crate::inputs::sample(2021, 14).unwrap();
+        let day = Day::from_str(&input).unwrap();
         b.iter(|| {
-            day.part2();
+            day.part2().unwrap();
         })
     }
 }
@@ -6,8 +6,8 @@ use std::{
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -18,27 +18,38 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let input = input
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let input: Input<usize> = input
             .lines()
-            .map(|l| l.chars().map(|c| c as usize - '0' as usize).collect())
-            .collect();
-        Self { input }
+            .map(|l| {
+                l.chars()
+                    .map(|c| {
+                        c.to_digit(10)
+                            .map(|r| r as usize)
+                            .ok_or_else(|| anyhow::anyhow!("invalid risk level {:?}", c))
+                    })
+                    .collect()
+            })
+            .collect::<anyhow::Result<_>>()?;
+        if input.is_empty() || input.iter().any(|row| row.len() != input[0].len()) {
+            return Err(anyhow::anyhow!("risk map is not a non-empty rectangle"));
+        }
+        Ok(Self { input })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut map = Astar::with(
             &self.input,
             (0, 0),
             (self.input[0].len() - 1, self.input.len() - 1),
         );
 
-        map.astar().to_string()
+        Ok(map.astar().into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let height = self.input.len();
         let width = self.input[0].len();
         let mut real_input = vec![vec![0; width * 5]; height * 5];
@@ -54,7 +65,7 @@ impl crate::Day for Day {
         }
         let mut map = Astar::with(&real_input, (0, 0), (width * 5 - 1, height * 5 - 1));
 
-        map.astar().to_string()
+        Ok(map.astar().into())
     }
 }
 
@@ -197,10 +208,10 @@ mod tests {
 
     #[bench]
     fn part2(b: &mut test::Bencher) {
-        let input = &crate::YEARS[&2021][&15];
-        let day = Day::from_str(input.1);
+        let input = crate::inputs::input(2021, 15).unwrap();
+        let day = Day::from_str(&input).unwrap();
         b.iter(|| {
-            day.part2();
+            day.part2().unwrap();
         })
     }
 }
@@ -3,8 +3,8 @@ use itertools::Itertools as _;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -15,23 +15,27 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
         let input = input
             .chars()
             .chunks(2)
             .into_iter()
             .map(|c| {
-                c.into_iter()
-                    .fold(0u8, |acc, c| acc << 4 | c.to_digit(16).unwrap() as u8)
+                c.into_iter().try_fold(0u8, |acc, c| {
+                    let nibble = c
+                        .to_digit(16)
+                        .ok_or_else(|| anyhow::anyhow!("invalid hex digit {:?}", c))?;
+                    Ok(acc << 4 | nibble as u8)
+                })
             })
-            .collect();
-        Self { input }
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { input })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        let p = parsers::parse(&self.input);
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        let p = parsers::parse(&self.input)?;
         let mut sum = 0;
         let mut to_visit = vec![p];
         while let Some(pack) = to_visit.pop() {
@@ -48,12 +52,12 @@ impl crate::Day for Day {
                 }
             }
         }
-        sum.to_string()
+        Ok(sum.into())
     }
 
-    fn part2(&self) -> String {
-        let p = parsers::parse(&self.input);
-        p.value().to_string()
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        let p = parsers::parse(&self.input)?;
+        Ok(p.value().into())
     }
 }
 
@@ -170,7 +174,9 @@ mod parsers {
                     if length % 8 != 0 {
                         inp.push(l_inp << (8 - length % 8));
                     }
-                    let (_, packets) = many0(packet)((&inp[..], 0)).unwrap();
+                    let (_, packets) = many0(packet)((&inp[..], 0)).map_err(|_| {
+                        nom::Err::Failure(nom::error::make_error(input, ErrorKind::Many0))
+                    })?;
                     (rest, packets)
                 } else {
                     count(packet, length)(input)?
@@ -183,34 +189,16 @@ mod parsers {
                         2 => Data::Min(data),
                         3 => Data::Max(data),
                         5 => {
-                            let mut data = data;
-                            let b = data
-                                .pop()
-                                .expect("At least 2 subpackets in greater packets");
-                            let a = data
-                                .pop()
-                                .expect("At least 2 subpackets in greater packets");
-                            Data::Greater(Box::new(a), Box::new(b))
+                            let (_, (a, b)) = operands(input, data)?;
+                            Data::Greater(a, b)
                         }
                         6 => {
-                            let mut data = data;
-                            let b = data
-                                .pop()
-                                .expect("At least 2 subpackets in greater packets");
-                            let a = data
-                                .pop()
-                                .expect("At least 2 subpackets in greater packets");
-                            Data::Less(Box::new(a), Box::new(b))
+                            let (_, (a, b)) = operands(input, data)?;
+                            Data::Less(a, b)
                         }
                         7 => {
-                            let mut data = data;
-                            let b = data
-                                .pop()
-                                .expect("At least 2 subpackets in greater packets");
-                            let a = data
-                                .pop()
-                                .expect("At least 2 subpackets in greater packets");
-                            Data::Equal(Box::new(a), Box::new(b))
+                            let (_, (a, b)) = operands(input, data)?;
+                            Data::Equal(a, b)
                         }
                         _ => {
                             return Err(nom::Err::Failure(nom::error::make_error(
@@ -224,6 +212,19 @@ mod parsers {
         }
     }
 
+    type Operands = (Box<Packet>, Box<Packet>);
+
+    /// The last two subpackets of a comparison.
+    fn operands(input: (&[u8], usize), mut data: Vec<Packet>) -> IResult<(&[u8], usize), Operands> {
+        match (data.pop(), data.pop()) {
+            (Some(b), Some(a)) => Ok((input, (Box::new(a), Box::new(b)))),
+            _ => Err(nom::Err::Failure(nom::error::make_error(
+                input,
+                ErrorKind::Count,
+            ))),
+        }
+    }
+
     fn packet(input: (&[u8], usize)) -> IResult<(&[u8], usize), Packet> {
         let (input, (version, type_id)) = tuple((version, type_id))(input)?;
 
@@ -233,8 +234,10 @@ mod parsers {
     fn parse_input(input: &[u8]) -> IResult<&[u8], Packet> {
         bits(packet)(input)
     }
-    pub fn parse(input: &[u8]) -> Packet {
-        parse_input(input).unwrap().1
+    pub fn parse(input: &[u8]) -> anyhow::Result<Packet> {
+        parse_input(input)
+            .map(|(_, packet)| packet)
+            .map_err(|e| anyhow::anyhow!("invalid packet: {:?}", e))
     }
 }
 
@@ -246,27 +249,27 @@ mod tests {
     #[test]
     fn part1_s1_test() {
         let input = "8A004A801A8002F478";
-        let day = Day::from_str(input);
-        assert_eq!("16", day.part1());
+        let day = Day::from_str(input).unwrap();
+        assert_eq!(crate::Answer::Integer(16), day.part1().unwrap());
     }
 
     #[test]
     fn literal_packet_test() {
         let input = "D2FE28";
-        let day = Day::from_str(input);
+        let day = Day::from_str(input).unwrap();
         assert_eq!(
             Packet {
                 version: 6,
                 data: Data::Literal(2021)
             },
-            parsers::parse(&day.input[..])
+            parsers::parse(&day.input[..]).unwrap()
         );
     }
 
     #[test]
     fn operator_packet_01_test() {
         let input = "38006F45291200";
-        let day = Day::from_str(input);
+        let day = Day::from_str(input).unwrap();
         assert_eq!(
             Packet {
                 version: 1,
@@ -281,14 +284,14 @@ mod tests {
                     }),
                 )
             },
-            parsers::parse(&day.input[..])
+            parsers::parse(&day.input[..]).unwrap()
         );
     }
 
     #[test]
     fn operator_packet_02_test() {
         let input = "EE00D40C823060";
-        let day = Day::from_str(input);
+        let day = Day::from_str(input).unwrap();
         assert_eq!(
             Packet {
                 version: 7,
@@ -307,7 +310,14 @@ mod tests {
                     },
                 ])
             },
-            parsers::parse(&day.input[..])
+            parsers::parse(&day.input[..]).unwrap()
         );
     }
+
+    #[test]
+    fn malformed_packet_test() {
+        // a greater packet with a single subpacket
+        let day = Day::from_str("16004408").unwrap();
+        assert!(day.part2().is_err());
+    }
 }
@@ -3,8 +3,8 @@ use std::ops::RangeInclusive;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -13,10 +13,10 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        Self {
-            input: parsers::parse_input(input),
-        }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: parsers::parse_input(input)?,
+        })
     }
 
     fn simulate(&self, mut vy: i32, mut vx: i32) -> bool {
@@ -61,16 +61,16 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut max_y = 0;
         self.run(|vy| max_y = max_y.max((vy * (vy + 1)) / 2));
-        max_y.to_string()
+        Ok(max_y.into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut n = 0;
         self.run(|_| n += 1);
-        n.to_string()
+        Ok(n.into())
     }
 }
 
@@ -86,8 +86,10 @@ mod parsers {
         IResult,
     };
 
-    pub fn parse_input(input: &str) -> (RangeInclusive<i32>, RangeInclusive<i32>) {
-        all_consuming(parse)(input).unwrap().1
+    pub fn parse_input(input: &str) -> anyhow::Result<(RangeInclusive<i32>, RangeInclusive<i32>)> {
+        all_consuming(parse)(input)
+            .map(|(_, target)| target)
+            .map_err(|e| e.to_owned().into())
     }
 
     fn parse(input: &str) -> IResult<&str, (RangeInclusive<i32>, RangeInclusive<i32>)> {
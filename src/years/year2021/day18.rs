@@ -3,8 +3,8 @@ use itertools::Itertools as _;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -221,32 +221,34 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(numbers: &str) -> Self {
-        Self {
-            numbers: parsers::parse(numbers),
-        }
+    pub fn from_str(numbers: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            numbers: parsers::parse(numbers)?,
+        })
     }
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.numbers
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self
+            .numbers
             .iter()
             .cloned()
             .sum::<SnailNumber>()
             .magnitude()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
-        self.numbers
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self
+            .numbers
             .iter()
             .cloned()
             .permutations(2)
             .map(|mut pair| (pair.pop().unwrap() + pair.pop().unwrap()).magnitude())
             .max()
-            .unwrap()
-            .to_string()
+            .ok_or_else(|| anyhow::anyhow!("need at least two snailfish numbers"))?
+            .into())
     }
 }
 
@@ -261,8 +263,10 @@ mod parsers {
         IResult,
     };
 
-    pub fn parse(input: &str) -> Vec<SnailNumber> {
-        all_consuming(numbers)(input).unwrap().1
+    pub fn parse(input: &str) -> anyhow::Result<Vec<SnailNumber>> {
+        all_consuming(numbers)(input)
+            .map(|(_, numbers)| numbers)
+            .map_err(|e| e.to_owned().into())
     }
 
     fn numbers(input: &str) -> IResult<&str, Vec<SnailNumber>> {
@@ -40,13 +40,7 @@ impl Report {
             .map(|b| array_init::array_init(|i| rotation[i].0 * b[rotation[i].1] + offset[i]))
             .collect();
         if rotated.iter().filter(|&b| self.beacons.contains(b)).count() >= 12 {
-            let mut matched = 0;
-            rotated.into_iter().for_each(|b| {
-                if !self.beacons.insert(b) {
-                    matched += 1;
-                }
-            });
-            assert!(matched >= 12);
+            self.beacons.extend(rotated);
             return true;
         }
         false
@@ -121,10 +115,10 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        Self {
-            input: parsers::parse_input(input).unwrap(),
-        }
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            input: parsers::parse_input(input)?,
+        })
     }
 }
 type Offset = [i64;3];
@@ -159,7 +153,7 @@ fn matches_to_hints(
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut base: Vec<_> = self
             .input
             .iter()
@@ -195,10 +189,13 @@ impl crate::Day for Day {
             }
         }
 
-        base[0].0.beacons.len().to_string()
+        Ok(base
+            .first()
+            .map_or(0, |(report, _)| report.beacons.len())
+            .into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut joins = Vec::with_capacity(self.input.len());
         let mut base: Vec<_> = self
             .input
@@ -236,20 +233,20 @@ impl crate::Day for Day {
             }
         }
 
-        Iterator::max(joins
+        Ok(Iterator::max(joins
             .iter()
             .map(|(_, _, [x, y, z], _)| [x, y, z])
             .permutations(2)
             .map(|pair| (pair[0], pair[1]))
             .map(|([x, y, z], [x2, y2, z2])| (x-x2).abs() + (y-y2).abs() + (z-z2).abs()))
-            .unwrap()
-            .to_string()
+            .unwrap_or_default()
+            .into())
     }
 }
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -15,8 +15,8 @@ const DIRECTIONS: [(i64, i64); 9] = [
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -57,9 +57,17 @@ struct Day {
 }
 
 impl Day {
-    pub fn from_str(input: &str) -> Self {
-        let (convert, image) = input.split_once("\n\n").unwrap();
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let (convert, image) = input
+            .split_once("\n\n")
+            .ok_or_else(|| anyhow::anyhow!("expected enhancement algorithm and image"))?;
         let convert: Vec<_> = convert.chars().map(|x| x == '#').collect();
+        if convert.len() != 512 {
+            return Err(anyhow::anyhow!(
+                "enhancement algorithm has {} entries instead of 512",
+                convert.len()
+            ));
+        }
         let image = image
             .lines()
             .enumerate()
@@ -69,7 +77,7 @@ impl Day {
                     .filter_map(move |(x, c)| (c == '#').then(|| (y as i64, x as i64)))
             })
             .collect();
-        Self { convert, image }
+        Ok(Self { convert, image })
     }
 
     fn step(&self, image: &Image) -> Image {
@@ -114,12 +122,12 @@ impl Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
-        self.run(2).to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(2).into())
     }
 
-    fn part2(&self) -> String {
-        self.run(50).to_string()
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok(self.run(50).into())
     }
 }
 
@@ -139,7 +147,7 @@ mod tests {
             "#",
         );
 
-        let day = Day::from_str(input);
+        let day = Day::from_str(input).unwrap();
         assert_eq!(day.convert, [true; 512]);
         assert_eq!(day.image.image, HashSet::from([(0, 0)]));
     }
@@ -152,7 +160,7 @@ mod tests {
             "#",
         );
 
-        let day = Day::from_str(input);
+        let day = Day::from_str(input).unwrap();
         assert_eq!(day.image.image, HashSet::from([(0, 0)]));
 
         let image2 = day.step(&day.image);
@@ -167,7 +175,7 @@ mod tests {
             "#",
         );
 
-        let mut day = Day::from_str(input);
+        let mut day = Day::from_str(input).unwrap();
         assert_eq!(day.image.image, HashSet::from([(0, 0)]));
 
         day.image.inverted = true;
@@ -3,8 +3,8 @@ use cached::proc_macro::cached;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Day::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)))
     }
 }
 
@@ -60,7 +60,7 @@ fn run(
     (res.1, res.0)
 }
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut die = 1;
         let mut n_rolled = 0;
         let mut p1 = self.input.0 - 1;
@@ -100,18 +100,18 @@ impl crate::Day for Day {
             p1turn = !p1turn;
         }
 
-        if points1 > points2 {
+        Ok(if points1 > points2 {
             points2 * n_rolled
         } else {
             points1 * n_rolled
         }
-        .to_string()
+        .into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let p1 = self.input.0 - 1;
         let p2 = self.input.1 - 1;
         let (p1wins, p2wins) = run(p1, p2, 0, 0, [0; 3]);
-        p1wins.max(p2wins).to_string()
+        Ok(p1wins.max(p2wins).into())
     }
 }
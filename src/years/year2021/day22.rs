@@ -109,7 +109,7 @@ struct Day {
 }
 
 impl crate::Day for Day {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let universe = self
             .instructions
             .iter()
@@ -135,14 +135,14 @@ impl crate::Day for Day {
                 universe
             });
 
-        universe
+        Ok(universe
             .into_iter()
             .map(|c| c.size())
             .sum::<i64>()
-            .to_string()
+            .into())
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let universe =
             self.instructions
                 .iter()
@@ -157,23 +157,19 @@ impl crate::Day for Day {
                     }
                     universe
                 });
-        universe
+        Ok(universe
             .into_iter()
             .map(|c| c.size())
             .sum::<i64>()
-            .to_string()
+            .into())
     }
 }
 
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let day = Day::from_str(input);
-        match day {
-            Ok(day) => Box::new(day),
-            Err(e) => panic!("{}", e),
-        }
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Day::from_str(input)?))
     }
 }
 
@@ -357,6 +353,6 @@ mod tests {
             "on x=10..10,y=10..10,z=10..10",
         );
         let day = Day::from_str(input).unwrap();
-        assert_eq!(crate::Day::part1(&day), "39");
+        assert_eq!(crate::Day::part1(&day).unwrap(), crate::Answer::Integer(39));
     }
 }
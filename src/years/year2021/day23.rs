@@ -180,11 +180,13 @@ fn is_sorted_bottom(i: usize, room: &[Space]) -> bool {
 }
 
 impl crate::Day for House {
-    fn part1(&self) -> String {
-        minimal_energy_to_sort(self.clone()).unwrap().to_string()
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        minimal_energy_to_sort(self.clone())
+            .map(|e| e.into())
+            .ok_or_else(|| anyhow::anyhow!("the amphipods cannot be sorted"))
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         let mut expanded = self.clone();
         expanded.rooms[0].insert(1, Some(Ambipod::Desert));
         expanded.rooms[0].insert(1, Some(Ambipod::Desert));
@@ -194,7 +196,9 @@ impl crate::Day for House {
         expanded.rooms[2].insert(1, Some(Ambipod::Bronze));
         expanded.rooms[3].insert(1, Some(Ambipod::Copper));
         expanded.rooms[3].insert(1, Some(Ambipod::Amber));
-        minimal_energy_to_sort(expanded).unwrap().to_string()
+        minimal_energy_to_sort(expanded)
+            .map(|e| e.into())
+            .ok_or_else(|| anyhow::anyhow!("the amphipods cannot be sorted"))
     }
 }
 
@@ -250,9 +254,9 @@ impl Ambipod {
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        let house: House = input.parse().expect("parse input");
-        Box::new(house)
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        let house: House = input.parse()?;
+        Ok(Box::new(house))
     }
 }
 
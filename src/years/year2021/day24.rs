@@ -5,8 +5,8 @@ use itertools::iproduct;
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(Alu::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(Alu::from_str(input)?))
     }
 }
 
@@ -29,7 +29,7 @@ enum Reg {
     Z = 3,
 }
 impl std::str::FromStr for Reg {
-    type Err = i64;
+    type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         use Reg::*;
@@ -38,11 +38,20 @@ impl std::str::FromStr for Reg {
             "x" | "X" => X,
             "y" | "Y" => Y,
             "z" | "Z" => Z,
-            _ => return Err(s.parse().unwrap()),
+            _ => return Err(anyhow::anyhow!("invalid register {:?}", s)),
         })
     }
 }
 
+fn operand(s: &str) -> anyhow::Result<Result<Reg, i64>> {
+    Ok(match s.parse() {
+        Ok(reg) => Ok(reg),
+        Err(_) => Err(s
+            .parse()
+            .map_err(|_| anyhow::anyhow!("invalid operand {:?}", s))?),
+    })
+}
+
 #[derive(Copy, Clone, Debug)]
 pub struct Memory([i64; 4]);
 
@@ -82,49 +91,72 @@ struct Alu {
 }
 
 impl Alu {
-    pub fn from_str(input: &str) -> Self {
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
         let program = input
             .lines()
             .map(|l| {
-                let (instruction, parameters) = l.split_once(' ').unwrap();
+                let (instruction, parameters) = l
+                    .split_once(' ')
+                    .ok_or_else(|| anyhow::anyhow!("missing parameters in {:?}", l))?;
                 let instruction = match instruction {
-                    "inp" => return Inp(parameters.parse().unwrap()),
+                    "inp" => return Ok(Inp(parameters.parse()?)),
                     "add" => Add,
                     "mul" => Mul,
                     "div" => Div,
                     "mod" => Mod,
                     "eql" => Eql,
-                    _ => panic!("invalid instruction {:?}", instruction),
+                    _ => return Err(anyhow::anyhow!("invalid instruction {:?}", instruction)),
                 };
-                let (param_a, param_b) = parameters.split_once(' ').unwrap();
-                instruction(param_a.parse().unwrap(), param_b.parse())
+                let (param_a, param_b) = parameters
+                    .split_once(' ')
+                    .ok_or_else(|| anyhow::anyhow!("missing second parameter in {:?}", l))?;
+                Ok(instruction(param_a.parse()?, operand(param_b)?))
             })
-            .collect();
-        Self { program }
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let inputs = program.iter().filter(|ins| matches!(ins, Inp(_))).count();
+        if inputs != LEN {
+            return Err(anyhow::anyhow!(
+                "the program reads {} digits instead of {}",
+                inputs,
+                LEN
+            ));
+        }
+        Ok(Self { program })
     }
 
-    fn run(&self, input: &[i64]) -> i64 {
+    fn run(&self, input: &[i64]) -> anyhow::Result<i64> {
         let mut input = input.iter();
         let mut memory = Memory::new();
 
         for instruction in &self.program {
             match instruction {
                 Inp(a) => {
-                    memory[*a] = *input.next().unwrap();
+                    memory[*a] = *input.next().ok_or_else(|| {
+                        anyhow::anyhow!("the program reads more than {} digits", LEN)
+                    })?;
                 }
                 Add(a, b) => memory[*a] += b.map_or_else(|i| i, |r| memory[r]),
                 Mul(a, b) => memory[*a] *= b.map_or_else(|i| i, |r| memory[r]),
-                Div(a, b) => memory[*a] /= b.map_or_else(|i| i, |r| memory[r]),
-                Mod(a, b) => memory[*a] %= b.map_or_else(|i| i, |r| memory[r]),
+                Div(a, b) => {
+                    memory[*a] = memory[*a]
+                        .checked_div(b.map_or_else(|i| i, |r| memory[r]))
+                        .ok_or_else(|| anyhow::anyhow!("invalid division in {:?}", instruction))?
+                }
+                Mod(a, b) => {
+                    memory[*a] = memory[*a]
+                        .checked_rem(b.map_or_else(|i| i, |r| memory[r]))
+                        .ok_or_else(|| anyhow::anyhow!("invalid modulo in {:?}", instruction))?
+                }
                 Eql(a, b) => {
                     memory[*a] = (memory[*a] == b.map_or_else(|i| i, |r| memory[r])) as i64
                 }
             }
         }
 
-        memory[Reg::Z]
+        Ok(memory[Reg::Z])
     }
-    fn pairs(&self) -> Vec<(usize, usize)> {
+    fn pairs(&self) -> anyhow::Result<Vec<(usize, usize)>> {
+        let unmatched = || anyhow::anyhow!("the program divides z by 26 without a matching digit");
         let mut pairs = Vec::new();
         let mut openers = Vec::new();
         let mut i = 0;
@@ -138,31 +170,31 @@ impl Alu {
             false
         }) {
             if let Div(..) = ins {
-                let b = openers.pop().unwrap();
-                pairs.push((openers.pop().unwrap(), b))
+                let b = openers.pop().ok_or_else(unmatched)?;
+                pairs.push((openers.pop().ok_or_else(unmatched)?, b))
             } else {
                 openers.push(i);
                 i += 1;
             }
         }
         pairs.sort_by_key(|(a, b)| b - a);
-        pairs
+        Ok(pairs)
     }
 
-    fn first_valid<It>(&self, it: It) -> String
+    fn first_valid<It>(&self, it: It) -> anyhow::Result<crate::Answer>
     where
         It: Iterator<Item = (i64, i64)> + Clone,
     {
-        let pairs = self.pairs();
+        let pairs = self.pairs()?;
         let mut digits = vec![9; LEN];
 
         for (ia, ib) in pairs {
-            let mut z_pre = self.run(&digits);
+            let mut z_pre = self.run(&digits)?;
             let (mut a_pre, mut b_pre) = (9, 9);
             for (a, b) in it.clone() {
                 digits[ia] = a;
                 digits[ib] = b;
-                let z = self.run(&digits);
+                let z = self.run(&digits)?;
                 match (z as f64)
                     .log(26.)
                     .ceil()
@@ -184,7 +216,7 @@ impl Alu {
             }
         }
 
-        digits.iter().map(i64::to_string).collect()
+        Ok(digits.iter().map(i64::to_string).collect::<String>().into())
     }
 }
 
@@ -220,11 +252,11 @@ impl Digits {
 
 const LEN: usize = 14;
 impl crate::Day for Alu {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         self.first_valid(iproduct!((1..=9).rev(), (1..=9).rev()))
     }
 
-    fn part2(&self) -> String {
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
         self.first_valid(iproduct!(1..=9, 1..=9))
     }
 }
@@ -3,8 +3,8 @@ use std::ops::{Index, IndexMut};
 pub struct DayGen;
 
 impl crate::DayGen for DayGen {
-    fn input(&self, input: &str) -> Box<dyn crate::Day> {
-        Box::new(SeaFloor::from_str(input))
+    fn input(&self, input: &str) -> anyhow::Result<Box<dyn crate::Day>> {
+        Ok(Box::new(SeaFloor::from_str(input)?))
     }
 }
 
@@ -15,10 +15,15 @@ struct SeaFloor {
 }
 
 impl SeaFloor {
-    pub fn from_str(input: &str) -> Self {
-        let width = input.lines().next().unwrap().chars().count();
+    pub fn from_str(input: &str) -> anyhow::Result<Self> {
+        let width = input
+            .lines()
+            .next()
+            .map(|l| l.chars().count())
+            .filter(|w| *w > 0)
+            .ok_or_else(|| anyhow::anyhow!("empty sea floor"))?;
         let area = input.lines().flat_map(str::chars).map(Tile::from).collect();
-        Self { width, area }
+        Ok(Self { width, area })
     }
 
     fn width(&self) -> usize {
@@ -101,18 +106,18 @@ impl From<char> for Tile {
 }
 
 impl crate::Day for SeaFloor {
-    fn part1(&self) -> String {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
         let mut floor = self.clone();
         let mut i = 0;
         while floor.step() {
             i += 1
         }
 
-        (i + 1).to_string()
+        Ok((i + 1).into())
     }
 
-    fn part2(&self) -> String {
-        "Nothing to be done!".to_string()
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        Ok("Nothing to be done!".into())
     }
 }
 
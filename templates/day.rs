@@ -0,0 +1,57 @@
+pub struct DayGen;
+
+impl crate::DayGen for DayGen {
+    fn input<'a>(&self, input: &'a str) -> anyhow::Result<Box<dyn crate::Day + 'a>> {
+        Ok(Box::new(Day::from_str(input)?))
+    }
+}
+
+type Input<'a> = Vec<&'a str>;
+
+struct Day<'a> {
+    input: Input<'a>,
+}
+
+impl<'a> Day<'a> {
+    pub fn from_str(input: &'a str) -> anyhow::Result<Self> {
+        let input = input.lines().collect();
+        Ok(Self { input })
+    }
+}
+
+impl<'a> crate::Day for Day<'a> {
+    fn part1(&self) -> anyhow::Result<crate::Answer> {
+        let _input = &self.input;
+        Ok(crate::Answer::Unimplemented)
+    }
+
+    fn part2(&self) -> anyhow::Result<crate::Answer> {
+        let _input = &self.input;
+        Ok(crate::Answer::Unimplemented)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day as _;
+
+    #[test]
+    fn samples_test() {
+        let dir = crate::inputs::crate_dir();
+        // there is nothing to check until the samples of the puzzle are added
+        if !crate::inputs::has_samples(&dir, {{year}}, {{day}}) {
+            return;
+        }
+        let samples = crate::inputs::samples(&dir, {{year}}, {{day}}).unwrap();
+        for sample in samples {
+            let day = Day::from_str(&sample.text).unwrap();
+            if let Some(expected) = sample.part1 {
+                assert_eq!(day.part1().unwrap(), expected, "{}", sample.name);
+            }
+            if let Some(expected) = sample.part2 {
+                assert_eq!(day.part2().unwrap(), expected, "{}", sample.name);
+            }
+        }
+    }
+}